@@ -0,0 +1,136 @@
+//! Criterion benchmarks, run with `cargo bench`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::convert::TryInto;
+use std::hint::black_box;
+use try_collect::partial_array::PartialArray;
+use try_collect::TryCollect;
+
+fn bench<R>(c: &mut Criterion, name: &str, mut f: impl FnMut() -> R) {
+    c.bench_function(name, |b| b.iter(&mut f));
+}
+
+fn manual_loop<const N: usize>(mut iter: impl Iterator<Item = u32>) -> Option<[u32; N]> {
+    let mut array = [0; N];
+    for slot in &mut array {
+        *slot = iter.next()?;
+    }
+    if iter.next().is_some() {
+        return None;
+    }
+    Some(array)
+}
+
+/// The `next()`-based fill loop `try_collect()` used before it switched to `try_fold()`.
+fn for_loop<const N: usize>(iter: impl Iterator<Item = u32>) -> Option<[u32; N]> {
+    let mut partial = PartialArray::<u32, N>::new();
+    for val in iter {
+        if partial.full() {
+            return None;
+        }
+        partial.push(val);
+    }
+    partial.try_into_array().ok()
+}
+
+fn overhead<const N: usize>(c: &mut Criterion) {
+    let source = || (0..N as u32).map(black_box);
+    bench(c, &format!("range/try_collect/{}", N), || {
+        source().try_collect::<[u32; N]>()
+    });
+    bench(c, &format!("range/manual_loop/{}", N), || {
+        manual_loop::<N>(source())
+    });
+    bench(c, &format!("range/vec_try_into/{}", N), || {
+        let result: Result<[u32; N], _> = source().collect::<Vec<_>>().try_into();
+        result
+    });
+}
+
+fn exact_size(c: &mut Criterion) {
+    // An array source keeps allocation out of the timed closure.
+    let source: [u32; 64] = core::array::from_fn(|i| i as u32);
+    bench(c, "array_into_iter/try_collect/64", || {
+        IntoIterator::into_iter(black_box(source)).try_collect::<[u32; 64]>()
+    });
+    bench(c, "array_into_iter/try_collect_exact_size/64", || {
+        IntoIterator::into_iter(black_box(source)).try_collect_exact_size::<64>()
+    });
+}
+
+fn copied(c: &mut Criterion) {
+    let source = vec![7u8; 4096];
+    bench(c, "slice_iter/copied_try_collect/4096", || {
+        black_box(&source)
+            .iter()
+            .copied()
+            .try_collect::<[u8; 4096]>()
+    });
+    bench(c, "slice_iter/try_collect_copied/4096", || {
+        black_box(&source).iter().try_collect_copied::<4096>()
+    });
+}
+
+fn chain(c: &mut Criterion) {
+    let source = || (0..512u32).chain(512..1024).map(black_box);
+    bench(c, "chain/try_collect/1024", || {
+        source().try_collect::<[u32; 1024]>()
+    });
+    bench(
+        c,
+        "chain/manual_loop/1024",
+        || manual_loop::<1024>(source()),
+    );
+    bench(c, "chain/for_loop/1024", || for_loop::<1024>(source()));
+    let source = || (0..32u32).flat_map(|i| i * 32..(i + 1) * 32).map(black_box);
+    bench(c, "flat_map/try_collect/1024", || {
+        source().try_collect::<[u32; 1024]>()
+    });
+    bench(c, "flat_map/manual_loop/1024", || {
+        manual_loop::<1024>(source())
+    });
+    bench(c, "flat_map/for_loop/1024", || for_loop::<1024>(source()));
+}
+
+/// Hides the `TrustedLen` impl of the wrapped iterator to force the generic fill loop.
+struct Opaque<I>(I);
+
+impl<I: Iterator> Iterator for Opaque<I> {
+    type Item = I::Item;
+    fn next(&mut self) -> Option<I::Item> {
+        self.0.next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+fn trusted_len(c: &mut Criterion) {
+    // Only differs from the generic path with the nightly-only `trusted_len` feature enabled.
+    let source = || (0..1024u32).map(black_box);
+    bench(c, "trusted_len/try_collect/1024", || {
+        source().try_collect::<[u32; 1024]>()
+    });
+    bench(c, "opaque/try_collect/1024", || {
+        Opaque(source()).try_collect::<[u32; 1024]>()
+    });
+}
+
+fn overhead_small(c: &mut Criterion) {
+    overhead::<4>(c);
+}
+
+fn overhead_large(c: &mut Criterion) {
+    overhead::<1024>(c);
+}
+
+criterion_group!(
+    benches,
+    overhead_small,
+    overhead_large,
+    exact_size,
+    copied,
+    chain,
+    trusted_len
+);
+criterion_main!(benches);
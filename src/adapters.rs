@@ -0,0 +1,206 @@
+//! Iterator adapters returned by the methods of [`TryCollect`](crate::TryCollect).
+
+use crate::{partial_array::PartialArray, NonMatchingLenError, TryCollect};
+use core::iter::{Fuse, FusedIterator, Peekable};
+
+/// An iterator over non-overlapping arrays of `N` elements.
+///
+/// Returned by [`TryCollect::chunks_array()`](crate::TryCollect::chunks_array).
+pub struct ChunksArray<I: Iterator, const N: usize> {
+    iter: Fuse<I>,
+    remainder: Option<PartialArray<I::Item, N>>,
+}
+
+impl<I: Iterator, const N: usize> ChunksArray<I, N> {
+    pub(crate) fn new(iter: I) -> Self {
+        assert!(N != 0, "chunk size must be non-zero");
+        Self {
+            iter: iter.fuse(),
+            remainder: None,
+        }
+    }
+
+    /// Returns the trailing elements that didn't fill a whole chunk.
+    ///
+    /// This is `None` until the underlying iterator is exhausted, or if there were no trailing
+    /// elements.
+    pub fn into_remainder(self) -> Option<PartialArray<I::Item, N>> {
+        self.remainder
+    }
+}
+
+impl<I: Iterator, const N: usize> Iterator for ChunksArray<I, N> {
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = PartialArray::new();
+        chunk.extend(&mut self.iter);
+        match chunk.try_into_array() {
+            Ok(array) => Some(array),
+            Err(chunk) => {
+                if !chunk.is_empty() {
+                    self.remainder = Some(chunk);
+                }
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower / N, upper.map(|upper| upper / N))
+    }
+}
+
+impl<I: Iterator, const N: usize> FusedIterator for ChunksArray<I, N> {}
+
+/// An iterator over overlapping windows of `N` elements.
+///
+/// Returned by [`TryCollect::windows_array()`](crate::TryCollect::windows_array).
+pub struct WindowsArray<I: Iterator, const N: usize> {
+    iter: Fuse<I>,
+    // A ring buffer holding the current window, starting at index `start` once it is full.
+    window: PartialArray<I::Item, N>,
+    start: usize,
+}
+
+impl<I: Iterator, const N: usize> WindowsArray<I, N> {
+    pub(crate) fn new(iter: I) -> Self {
+        assert!(N != 0, "window size must be non-zero");
+        Self {
+            iter: iter.fuse(),
+            window: PartialArray::new(),
+            start: 0,
+        }
+    }
+}
+
+impl<I: Iterator, const N: usize> Iterator for WindowsArray<I, N>
+where
+    I::Item: Clone,
+{
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.window.full() {
+            // Replace the oldest element.
+            self.window.as_mut_slice()[self.start] = self.iter.next()?;
+            self.start = (self.start + 1) % N;
+        } else {
+            self.window.extend(&mut self.iter);
+            if !self.window.full() {
+                return None;
+            }
+        }
+        let window = self.window.as_slice();
+        Some(core::array::from_fn(|i| {
+            window[(self.start + i) % N].clone()
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let windows = |remaining: usize| {
+            if self.window.full() {
+                remaining
+            } else {
+                // The first window is only complete after `self.window.remaining()` elements.
+                remaining
+                    .saturating_add(1)
+                    .saturating_sub(self.window.remaining())
+            }
+        };
+        (windows(lower), upper.map(windows))
+    }
+}
+
+impl<I: Iterator, const N: usize> FusedIterator for WindowsArray<I, N> where I::Item: Clone {}
+
+/// A deferred collection into an array of `N` elements.
+///
+/// Returned by [`TryCollect::try_collect_lazy()`](crate::TryCollect::try_collect_lazy). The
+/// source iterator is only consumed by [`finish()`](LazyArrayCollect::finish), so its length
+/// can be inspected before deciding whether to collect.
+pub struct LazyArrayCollect<I: Iterator, const N: usize> {
+    iter: Peekable<I>,
+}
+
+impl<I: Iterator, const N: usize> LazyArrayCollect<I, N> {
+    pub(crate) fn new(iter: I) -> Self {
+        Self {
+            iter: iter.peekable(),
+        }
+    }
+
+    /// Returns the size hint of the source iterator.
+    pub fn remaining_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    /// Returns a reference to the first element without consuming it.
+    pub fn peek(&mut self) -> Option<&I::Item> {
+        self.iter.peek()
+    }
+
+    /// Collects the source iterator into the array.
+    pub fn finish(self) -> Result<[I::Item; N], NonMatchingLenError> {
+        self.iter.try_collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TryCollect;
+
+    #[test]
+    fn chunks_array() {
+        let mut chunks = (1..8).chunks_array::<3>();
+        assert_eq!(chunks.next(), Some([1, 2, 3]));
+        assert_eq!(chunks.next(), Some([4, 5, 6]));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.into_remainder().unwrap().as_slice(), [7]);
+    }
+
+    #[test]
+    fn chunks_array_exact() {
+        let mut chunks = (1..7).chunks_array::<2>();
+        assert_eq!(chunks.size_hint(), (3, Some(3)));
+        assert_eq!(chunks.by_ref().count(), 3);
+        assert!(chunks.into_remainder().is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn chunks_array_zero() {
+        (1..7).chunks_array::<0>();
+    }
+
+    #[test]
+    fn windows_array() {
+        let mut windows = (1..6).windows_array::<3>();
+        assert_eq!(windows.size_hint(), (3, Some(3)));
+        assert_eq!(windows.next(), Some([1, 2, 3]));
+        assert_eq!(windows.size_hint(), (2, Some(2)));
+        assert_eq!(windows.next(), Some([2, 3, 4]));
+        assert_eq!(windows.next(), Some([3, 4, 5]));
+        assert_eq!(windows.next(), None);
+    }
+
+    #[test]
+    fn windows_array_too_short() {
+        let mut windows = (1..3).windows_array::<3>();
+        assert_eq!(windows.size_hint(), (0, Some(0)));
+        assert_eq!(windows.next(), None);
+    }
+
+    #[test]
+    fn try_collect_lazy() {
+        let mut lazy = (1..4).try_collect_lazy::<3>();
+        assert_eq!(lazy.remaining_hint(), (3, Some(3)));
+        assert_eq!(lazy.peek(), Some(&1));
+        assert_eq!(lazy.remaining_hint(), (3, Some(3)));
+        assert_eq!(lazy.finish(), Ok([1, 2, 3]));
+        assert!((1..3).try_collect_lazy::<3>().finish().is_err());
+    }
+}
@@ -0,0 +1,151 @@
+//! A fixed-size array of booleans packed into bytes.
+//!
+//! Since the byte count can't be computed from `N` in a type on stable Rust, [`BitArray`] takes
+//! it as a second parameter, which must be equal to [`bytes_for_bits(N)`](bytes_for_bits):
+//!
+//! ```
+//! use try_collect::bit_array::{bytes_for_bits, BitArray};
+//! use try_collect::TryCollect;
+//!
+//! const N: usize = 10;
+//! let flags: BitArray<N, { bytes_for_bits(N) }> =
+//!     (0..N).map(|i| i % 3 == 0).try_collect().unwrap();
+//! assert!(flags.get(3));
+//! assert!(!flags.get(4));
+//! assert!(flags.iter().eq((0..N).map(|i| i % 3 == 0)));
+//! ```
+
+use crate::{check_size_hint, NonMatchingLenError, TryFromIterator};
+
+/// Returns the number of bytes needed to store `bits` bits.
+pub const fn bytes_for_bits(bits: usize) -> usize {
+    bits.div_ceil(8)
+}
+
+/// An array of `N` booleans, stored in `BYTES` bytes.
+///
+/// Bit `i` is stored in bit `i % 8` of byte `i / 8`, counting from the least significant bit.
+/// Unused bits of the last byte are always zero. `BYTES` must be equal to `bytes_for_bits(N)`,
+/// which is checked at compile time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitArray<const N: usize, const BYTES: usize> {
+    bytes: [u8; BYTES],
+}
+
+impl<const N: usize, const BYTES: usize> BitArray<N, BYTES> {
+    /// Returns the number of bits, `N`.
+    pub fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if `N` is zero.
+    pub fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Returns the bit at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is `N` or more.
+    pub fn get(&self, index: usize) -> bool {
+        assert!(index < N, "index {} out of range for {} bits", index, N);
+        self.bytes[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    /// Returns an iterator over the bits in index order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = bool> + '_ {
+        (0..N).map(move |index| self.get(index))
+    }
+
+    /// Returns the packed bytes.
+    pub fn as_bytes(&self) -> &[u8; BYTES] {
+        &self.bytes
+    }
+}
+
+/// Fails if the iterator doesn't yield exactly `N` booleans.
+impl<const N: usize, const BYTES: usize> TryFromIterator<bool> for BitArray<N, BYTES> {
+    type Error = NonMatchingLenError;
+    const EXPECTED_LEN: Option<usize> = Some(N);
+    fn try_from_iter<T>(iter: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator<Item = bool>,
+    {
+        const { assert!(BYTES == bytes_for_bits(N), "BYTES doesn't match N") };
+        let iter = iter.into_iter();
+        check_size_hint(N, iter.size_hint())?;
+        let mut bytes = [0; BYTES];
+        let mut actual = 0;
+        for bit in iter {
+            if actual == N {
+                return Err(NonMatchingLenError::TooLong {
+                    expected: N,
+                    actual: N + 1,
+                    consumed: N + 1,
+                });
+            }
+            bytes[actual / 8] |= (bit as u8) << (actual % 8);
+            actual += 1;
+        }
+        if actual < N {
+            return Err(NonMatchingLenError::TooShort {
+                expected: N,
+                actual,
+                consumed: actual,
+            });
+        }
+        Ok(Self { bytes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bytes_for_bits, BitArray};
+    use crate::{NonMatchingLenError, TryCollect};
+
+    #[test]
+    fn bytes_for_bits_rounds_up() {
+        assert_eq!(bytes_for_bits(0), 0);
+        assert_eq!(bytes_for_bits(1), 1);
+        assert_eq!(bytes_for_bits(8), 1);
+        assert_eq!(bytes_for_bits(9), 2);
+    }
+
+    #[test]
+    fn try_collect_bit_array() {
+        let bits = [true, false, true, true, false, false, false, false, true];
+        let array: BitArray<9, 2> = bits.into_iter().try_collect().unwrap();
+        assert_eq!(array.as_bytes(), &[0b1101, 0b1]);
+        assert!(array.iter().eq(bits));
+        assert_eq!(
+            bits.into_iter()
+                .filter(|_| true)
+                .try_collect::<BitArray<8, 1>>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 8,
+                actual: 9,
+                consumed: 9,
+            })
+        );
+        assert_eq!(
+            bits.into_iter()
+                .chain(core::iter::from_fn(|| None))
+                .try_collect::<BitArray<10, 2>>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 10,
+                actual: 9,
+                consumed: 9,
+            })
+        );
+        let empty: BitArray<0, 0> = core::iter::empty().try_collect().unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn bit_array_get_out_of_range() {
+        let array: BitArray<3, 1> = [true; 3].into_iter().try_collect().unwrap();
+        array.get(3);
+    }
+}
@@ -0,0 +1,610 @@
+//! Growable collections with a compile-time upper bound on their size.
+//!
+//! Collecting into these types fails with a [`CapacityError`] as soon as the iterator yields more
+//! elements than the bound allows, which guards against untrusted input of unreasonable size.
+
+use crate::{CapacityError, FixedCapacity, TryExtend, TryFromIterator};
+use alloc::{
+    borrow::Cow,
+    collections::{BinaryHeap, VecDeque},
+    rc::Rc,
+    string::String,
+    vec::Vec,
+};
+use core::{fmt, ops::Deref};
+#[cfg(feature = "smallvec")]
+use smallvec::SmallVec;
+
+/// A `String` holding at most `MAX` chars.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoundedString<const MAX: usize> {
+    string: String,
+    chars: usize,
+}
+
+impl<const MAX: usize> BoundedString<MAX> {
+    /// Creates an empty `BoundedString`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a char, or returns it in the error if the string already holds `MAX` chars.
+    pub fn try_push(&mut self, c: char) -> Result<(), CapacityError<char>> {
+        if self.chars == MAX {
+            return Err(CapacityError::new(c, MAX));
+        }
+        self.string.push(c);
+        self.chars += 1;
+        Ok(())
+    }
+
+    /// Returns the string as a `&str`.
+    pub fn as_str(&self) -> &str {
+        &self.string
+    }
+
+    /// Converts into the underlying `String`.
+    pub fn into_string(self) -> String {
+        self.string
+    }
+}
+
+impl<const MAX: usize> Deref for BoundedString<MAX> {
+    type Target = str;
+    fn deref(&self) -> &str {
+        &self.string
+    }
+}
+
+impl<const MAX: usize> fmt::Display for BoundedString<MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.string.fmt(f)
+    }
+}
+
+/// The bound is measured in chars, not bytes.
+impl<const MAX: usize> TryFromIterator<char> for BoundedString<MAX> {
+    type Error = CapacityError<char>;
+    fn try_from_iter<T>(iter: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator<Item = char>,
+    {
+        let mut string = Self::new();
+        for c in iter {
+            string.try_push(c)?;
+        }
+        Ok(string)
+    }
+}
+
+impl<const MAX: usize> TryExtend<char> for BoundedString<MAX> {
+    type Error = CapacityError<char>;
+    fn try_extend<T>(&mut self, iter: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = char>,
+    {
+        iter.into_iter().try_for_each(|c| self.try_push(c))
+    }
+}
+
+/// The capacity is measured in chars.
+impl<const MAX: usize> FixedCapacity for BoundedString<MAX> {
+    const CAPACITY: usize = MAX;
+}
+
+/// A `Cow<str>` holding at most `MAX_BYTES` bytes of UTF-8.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoundedCowString<'a, const MAX_BYTES: usize> {
+    string: Cow<'a, str>,
+}
+
+impl<'a, const MAX_BYTES: usize> BoundedCowString<'a, MAX_BYTES> {
+    /// Creates an empty `BoundedCowString`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Borrows `s` without copying, or returns it in the error if it is longer than `MAX_BYTES`.
+    pub fn try_borrowed(s: &'a str) -> Result<Self, CapacityError<&'a str>> {
+        if s.len() > MAX_BYTES {
+            return Err(CapacityError::new(s, MAX_BYTES));
+        }
+        Ok(Self {
+            string: Cow::Borrowed(s),
+        })
+    }
+
+    /// Appends a char, or returns it in the error if its UTF-8 encoding doesn't fit into the
+    /// remaining bytes.
+    pub fn try_push(&mut self, c: char) -> Result<(), CapacityError<char>> {
+        if self.string.len() + c.len_utf8() > MAX_BYTES {
+            return Err(CapacityError::new(c, MAX_BYTES));
+        }
+        self.string.to_mut().push(c);
+        Ok(())
+    }
+
+    /// Returns the string as a `&str`.
+    pub fn as_str(&self) -> &str {
+        &self.string
+    }
+
+    /// Converts into the underlying `Cow<str>`.
+    pub fn into_cow(self) -> Cow<'a, str> {
+        self.string
+    }
+}
+
+impl<const MAX_BYTES: usize> Deref for BoundedCowString<'_, MAX_BYTES> {
+    type Target = str;
+    fn deref(&self) -> &str {
+        &self.string
+    }
+}
+
+impl<const MAX_BYTES: usize> fmt::Display for BoundedCowString<'_, MAX_BYTES> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.string.fmt(f)
+    }
+}
+
+/// The bound is measured in bytes, and is checked before pushing each char.
+impl<const MAX_BYTES: usize> TryFromIterator<char> for BoundedCowString<'_, MAX_BYTES> {
+    type Error = CapacityError<char>;
+    fn try_from_iter<T>(iter: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator<Item = char>,
+    {
+        let mut string = Self::new();
+        for c in iter {
+            string.try_push(c)?;
+        }
+        Ok(string)
+    }
+}
+
+impl<const MAX_BYTES: usize> TryExtend<char> for BoundedCowString<'_, MAX_BYTES> {
+    type Error = CapacityError<char>;
+    fn try_extend<T>(&mut self, iter: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = char>,
+    {
+        iter.into_iter().try_for_each(|c| self.try_push(c))
+    }
+}
+
+/// The capacity is measured in bytes.
+impl<const MAX_BYTES: usize> FixedCapacity for BoundedCowString<'_, MAX_BYTES> {
+    const CAPACITY: usize = MAX_BYTES;
+}
+
+/// A `VecDeque` holding at most `CAP` elements.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoundedVecDeque<T, const CAP: usize> {
+    deque: VecDeque<T>,
+}
+
+impl<T, const CAP: usize> BoundedVecDeque<T, CAP> {
+    /// Creates an empty `BoundedVecDeque`.
+    pub fn new() -> Self {
+        Self {
+            deque: VecDeque::new(),
+        }
+    }
+
+    /// Appends an element to the back, or returns it in the error if the deque already holds
+    /// `CAP` elements.
+    pub fn try_push_back(&mut self, val: T) -> Result<(), CapacityError<T>> {
+        if self.deque.len() == CAP {
+            return Err(CapacityError::new(val, CAP));
+        }
+        self.deque.push_back(val);
+        Ok(())
+    }
+
+    /// Removes the first element and returns it, or `None` if the deque is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.deque.pop_front()
+    }
+
+    /// Converts into the underlying `VecDeque`.
+    pub fn into_inner(self) -> VecDeque<T> {
+        self.deque
+    }
+}
+
+impl<T, const CAP: usize> Default for BoundedVecDeque<T, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const CAP: usize> Deref for BoundedVecDeque<T, CAP> {
+    type Target = VecDeque<T>;
+    fn deref(&self) -> &VecDeque<T> {
+        &self.deque
+    }
+}
+
+impl<T, const CAP: usize> TryFromIterator<T> for BoundedVecDeque<T, CAP> {
+    type Error = CapacityError<T>;
+    fn try_from_iter<I>(iter: I) -> Result<Self, Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut deque = Self::new();
+        for val in iter {
+            deque.try_push_back(val)?;
+        }
+        Ok(deque)
+    }
+}
+
+/// Pushes to the back.
+impl<T, const CAP: usize> TryExtend<T> for BoundedVecDeque<T, CAP> {
+    type Error = CapacityError<T>;
+    fn try_extend<I>(&mut self, iter: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        iter.into_iter().try_for_each(|val| self.try_push_back(val))
+    }
+}
+
+impl<T, const CAP: usize> FixedCapacity for BoundedVecDeque<T, CAP> {
+    const CAPACITY: usize = CAP;
+}
+
+/// A `SmallVec` storing up to `INLINE` elements inline and holding at most `MAX` elements.
+///
+/// The two bounds are independent: the vector spills to the heap once it holds more than
+/// `INLINE` elements, and fails to grow beyond `MAX` elements.
+#[cfg(feature = "smallvec")]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoundedSmallVec<T, const INLINE: usize, const MAX: usize> {
+    vec: SmallVec<[T; INLINE]>,
+}
+
+#[cfg(feature = "smallvec")]
+impl<T, const INLINE: usize, const MAX: usize> BoundedSmallVec<T, INLINE, MAX> {
+    /// Creates an empty `BoundedSmallVec`.
+    pub fn new() -> Self {
+        Self {
+            vec: SmallVec::new(),
+        }
+    }
+
+    /// Appends an element, or returns it in the error if the vector already holds `MAX`
+    /// elements.
+    pub fn try_push(&mut self, val: T) -> Result<(), CapacityError<T>> {
+        if self.vec.len() == MAX {
+            return Err(CapacityError::new(val, MAX));
+        }
+        self.vec.push(val);
+        Ok(())
+    }
+
+    /// Removes the last element and returns it, or `None` if the vector is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        self.vec.pop()
+    }
+
+    /// Converts into the underlying `SmallVec`.
+    pub fn into_inner(self) -> SmallVec<[T; INLINE]> {
+        self.vec
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<T, const INLINE: usize, const MAX: usize> Default for BoundedSmallVec<T, INLINE, MAX> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<T, const INLINE: usize, const MAX: usize> Deref for BoundedSmallVec<T, INLINE, MAX> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        &self.vec
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<T, const INLINE: usize, const MAX: usize> TryFromIterator<T>
+    for BoundedSmallVec<T, INLINE, MAX>
+{
+    type Error = CapacityError<T>;
+    fn try_from_iter<I>(iter: I) -> Result<Self, Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut vec = Self::new();
+        for val in iter {
+            vec.try_push(val)?;
+        }
+        Ok(vec)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<T, const INLINE: usize, const MAX: usize> TryExtend<T> for BoundedSmallVec<T, INLINE, MAX> {
+    type Error = CapacityError<T>;
+    fn try_extend<I>(&mut self, iter: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        iter.into_iter().try_for_each(|val| self.try_push(val))
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<T, const INLINE: usize, const MAX: usize> FixedCapacity for BoundedSmallVec<T, INLINE, MAX> {
+    const CAPACITY: usize = MAX;
+}
+
+/// A `BinaryHeap` holding at most `CAP` elements.
+#[derive(Clone, Debug)]
+pub struct BoundedBinaryHeap<T, const CAP: usize> {
+    heap: BinaryHeap<T>,
+}
+
+impl<T: Ord, const CAP: usize> BoundedBinaryHeap<T, CAP> {
+    /// Creates an empty `BoundedBinaryHeap`.
+    pub fn new() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    /// Pushes an element, or returns it in the error if the heap already holds `CAP` elements.
+    pub fn try_push(&mut self, val: T) -> Result<(), CapacityError<T>> {
+        if self.heap.len() == CAP {
+            return Err(CapacityError::new(val, CAP));
+        }
+        self.heap.push(val);
+        Ok(())
+    }
+
+    /// Removes the greatest element and returns it, or `None` if the heap is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    /// Converts into the underlying `BinaryHeap`.
+    pub fn into_inner(self) -> BinaryHeap<T> {
+        self.heap
+    }
+}
+
+impl<T: Ord, const CAP: usize> Default for BoundedBinaryHeap<T, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const CAP: usize> Deref for BoundedBinaryHeap<T, CAP> {
+    type Target = BinaryHeap<T>;
+    fn deref(&self) -> &BinaryHeap<T> {
+        &self.heap
+    }
+}
+
+impl<T: Ord, const CAP: usize> TryFromIterator<T> for BoundedBinaryHeap<T, CAP> {
+    type Error = CapacityError<T>;
+    fn try_from_iter<I>(iter: I) -> Result<Self, Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut heap = Self::new();
+        heap.try_extend(iter)?;
+        Ok(heap)
+    }
+}
+
+impl<T: Ord, const CAP: usize> TryExtend<T> for BoundedBinaryHeap<T, CAP> {
+    type Error = CapacityError<T>;
+    fn try_extend<I>(&mut self, iter: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        iter.into_iter().try_for_each(|val| self.try_push(val))
+    }
+}
+
+impl<T, const CAP: usize> FixedCapacity for BoundedBinaryHeap<T, CAP> {
+    const CAPACITY: usize = CAP;
+}
+
+macro_rules! bounded_shared_slice {
+    ($(#[$attr:meta])* $name:ident, $ptr:ident, $into:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name<T, const MAX: usize> {
+            slice: $ptr<[T]>,
+        }
+
+        impl<T, const MAX: usize> $name<T, MAX> {
+            #[doc = concat!("Converts into the underlying `", stringify!($ptr), "<[T]>`.")]
+            pub fn $into(self) -> $ptr<[T]> {
+                self.slice
+            }
+        }
+
+        impl<T, const MAX: usize> Clone for $name<T, MAX> {
+            fn clone(&self) -> Self {
+                Self {
+                    slice: self.slice.clone(),
+                }
+            }
+        }
+
+        impl<T, const MAX: usize> Deref for $name<T, MAX> {
+            type Target = [T];
+            fn deref(&self) -> &[T] {
+                &self.slice
+            }
+        }
+
+        /// The elements are collected into a `Vec` first, which is only moved into the shared
+        /// slice if the iterator doesn't exceed the bound.
+        impl<T, const MAX: usize> TryFromIterator<T> for $name<T, MAX> {
+            type Error = CapacityError<T>;
+            fn try_from_iter<I>(iter: I) -> Result<Self, Self::Error>
+            where
+                I: IntoIterator<Item = T>,
+            {
+                let mut vec = Vec::new();
+                for val in iter {
+                    if vec.len() == MAX {
+                        return Err(CapacityError::new(val, MAX));
+                    }
+                    vec.push(val);
+                }
+                Ok(Self { slice: vec.into() })
+            }
+        }
+
+        impl<T, const MAX: usize> FixedCapacity for $name<T, MAX> {
+            const CAPACITY: usize = MAX;
+        }
+    };
+}
+
+bounded_shared_slice! {
+    /// An `Rc<[T]>` holding at most `MAX` elements.
+    BoundedRcSlice, Rc, into_rc
+}
+
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
+#[cfg(target_has_atomic = "ptr")]
+bounded_shared_slice! {
+    /// An `Arc<[T]>` holding at most `MAX` elements.
+    BoundedArcSlice, Arc, into_arc
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(target_has_atomic = "ptr")]
+    use super::BoundedArcSlice;
+    use super::{
+        BoundedBinaryHeap, BoundedCowString, BoundedRcSlice, BoundedString, BoundedVecDeque,
+    };
+    use crate::{TryCollect, TryExtend};
+    use alloc::borrow::Cow;
+
+    #[test]
+    fn try_collect_bounded_string() {
+        let string: BoundedString<3> = "äöü".chars().try_collect().unwrap();
+        assert_eq!(string.as_str(), "äöü");
+        assert_eq!(string.len(), 6);
+        let err = "abcd"
+            .chars()
+            .try_collect::<BoundedString<3>>()
+            .unwrap_err();
+        assert_eq!(*err.element(), 'd');
+        assert_eq!(err.capacity(), 3);
+    }
+
+    #[test]
+    fn try_collect_bounded_cow_string() {
+        let string: BoundedCowString<5> = "aäö".chars().try_collect().unwrap();
+        assert_eq!(string.as_str(), "aäö");
+        let err = "aäöü"
+            .chars()
+            .try_collect::<BoundedCowString<6>>()
+            .unwrap_err();
+        assert_eq!(*err.element(), 'ü');
+        assert_eq!(err.capacity(), 6);
+    }
+
+    #[test]
+    fn bounded_cow_string_borrowed() {
+        let mut string = BoundedCowString::<4>::try_borrowed("abc").unwrap();
+        assert!(matches!(string.clone().into_cow(), Cow::Borrowed("abc")));
+        assert_eq!(string.try_push('ä').unwrap_err().into_element(), 'ä');
+        string.try_push('d').unwrap();
+        assert_eq!(string.as_str(), "abcd");
+        assert!(BoundedCowString::<2>::try_borrowed("abc").is_err());
+    }
+
+    #[test]
+    fn try_collect_bounded_vec_deque() {
+        let mut deque: BoundedVecDeque<_, 3> = (1..4).try_collect().unwrap();
+        assert_eq!(deque.try_push_back(4).unwrap_err().into_element(), 4);
+        assert_eq!(deque.pop_front(), Some(1));
+        deque.try_push_back(4).unwrap();
+        assert!(deque.iter().eq(&[2, 3, 4]));
+        let err = (1..5).try_collect::<BoundedVecDeque<_, 3>>().unwrap_err();
+        assert_eq!(*err.element(), 4);
+        assert_eq!(err.capacity(), 3);
+    }
+
+    #[test]
+    fn try_extend_bounded() {
+        let mut string = BoundedString::<4>::new();
+        string.try_extend("ab".chars()).unwrap();
+        assert_eq!(
+            string.try_extend("cde".chars()).unwrap_err().into_element(),
+            'e'
+        );
+        assert_eq!(string.as_str(), "abcd");
+        let mut string = BoundedCowString::<4>::try_borrowed("ab").unwrap();
+        assert!(string.try_extend("cä".chars()).is_err());
+        assert_eq!(string.as_str(), "abc");
+        let mut deque = BoundedVecDeque::<_, 3>::new();
+        deque.try_extend([1, 2]).unwrap();
+        assert!(deque.try_extend([3, 4]).is_err());
+        assert!(deque.iter().eq(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn try_collect_bounded_shared_slice() {
+        let slice: BoundedRcSlice<_, 3> = (1..3).try_collect().unwrap();
+        assert_eq!(*slice, [1, 2]);
+        assert_eq!(slice.clone().into_rc().len(), 2);
+        let err = (1..5).try_collect::<BoundedRcSlice<_, 3>>().unwrap_err();
+        assert_eq!(*err.element(), 4);
+    }
+
+    #[test]
+    #[cfg(target_has_atomic = "ptr")]
+    fn try_collect_bounded_arc_slice() {
+        let slice: BoundedArcSlice<_, 3> = (1..4).try_collect().unwrap();
+        assert_eq!(*slice.into_arc(), [1, 2, 3]);
+        let err = (1..5).try_collect::<BoundedArcSlice<_, 3>>().unwrap_err();
+        assert_eq!(*err.element(), 4);
+        assert_eq!(err.capacity(), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "smallvec")]
+    fn try_collect_bounded_small_vec() {
+        use super::BoundedSmallVec;
+        let vec: BoundedSmallVec<_, 2, 4> = (1..5).try_collect().unwrap();
+        assert_eq!(*vec, [1, 2, 3, 4]);
+        assert!(vec.clone().into_inner().spilled());
+        let err = (1..6)
+            .try_collect::<BoundedSmallVec<_, 2, 4>>()
+            .unwrap_err();
+        assert_eq!(*err.element(), 5);
+        assert_eq!(err.capacity(), 4);
+        let mut vec: BoundedSmallVec<_, 8, 3> = (1..3).try_collect().unwrap();
+        assert_eq!(vec.try_extend(3..5).unwrap_err().into_element(), 4);
+        assert!(!vec.into_inner().spilled());
+    }
+
+    #[test]
+    fn try_collect_bounded_binary_heap() {
+        let mut heap: BoundedBinaryHeap<_, 3> = [2, 3, 1].into_iter().try_collect().unwrap();
+        assert_eq!(heap.peek(), Some(&3));
+        assert_eq!(heap.try_push(4).unwrap_err().into_element(), 4);
+        assert_eq!(heap.pop(), Some(3));
+        heap.try_push(0).unwrap();
+        assert_eq!(heap.into_inner().into_sorted_vec(), [0, 1, 2]);
+        let err = (1..5).try_collect::<BoundedBinaryHeap<_, 3>>().unwrap_err();
+        assert_eq!(*err.element(), 4);
+    }
+}
@@ -0,0 +1,118 @@
+//! A builder for constructing arrays element by element.
+
+use crate::{partial_array::PartialArray, CapacityError, FixedCapacity, NonMatchingLenError};
+
+/// Builds an array of `N` elements through chained calls.
+///
+/// ```
+/// use try_collect::collector::ArrayCollector;
+///
+/// let array = ArrayCollector::<_, 3>::new().push(1).push(2).push(3).finish();
+/// assert_eq!(array, Ok([1, 2, 3]));
+/// ```
+///
+/// Dropping a partially built collector drops the elements pushed so far.
+#[derive(Clone, Debug)]
+pub struct ArrayCollector<T, const N: usize> {
+    partial: PartialArray<T, N>,
+}
+
+impl<T, const N: usize> ArrayCollector<T, N> {
+    /// Creates an empty `ArrayCollector`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            partial: PartialArray::new(),
+        }
+    }
+
+    /// Appends an element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` elements have already been pushed.
+    #[inline]
+    pub fn push(mut self, val: T) -> Self {
+        self.partial.push(val);
+        self
+    }
+
+    /// Appends an element, or returns it in the error if `N` elements have already been pushed.
+    ///
+    /// The collector is dropped on error, together with the elements pushed so far.
+    #[inline]
+    pub fn try_push(mut self, val: T) -> Result<Self, CapacityError<T>> {
+        if self.partial.full() {
+            return Err(CapacityError::new(val, N));
+        }
+        self.partial.push(val);
+        Ok(self)
+    }
+
+    /// Returns the array, or an error if fewer than `N` elements have been pushed.
+    #[inline]
+    pub fn finish(self) -> Result<[T; N], NonMatchingLenError> {
+        self.partial
+            .try_into_array()
+            .map_err(|partial| NonMatchingLenError::TooShort {
+                expected: N,
+                actual: partial.len(),
+                consumed: partial.len(),
+            })
+    }
+}
+
+impl<T, const N: usize> Default for ArrayCollector<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> FixedCapacity for ArrayCollector<T, N> {
+    const CAPACITY: usize = N;
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use super::ArrayCollector;
+    use crate::NonMatchingLenError;
+    use std::rc::Rc;
+
+    #[test]
+    fn array_collector_default() {
+        struct NonDefault(u8);
+        let collector = ArrayCollector::<NonDefault, 2>::default();
+        let array = collector.push(NonDefault(1)).push(NonDefault(2)).finish();
+        assert_eq!(array.map(|[a, b]| (a.0, b.0)), Ok((1, 2)));
+    }
+
+    #[test]
+    fn array_collector() {
+        let collector = ArrayCollector::<_, 3>::new().push(1).push(2);
+        assert_eq!(
+            collector.clone().finish(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 3,
+                actual: 2,
+                consumed: 2,
+            })
+        );
+        let collector = collector.try_push(3).unwrap();
+        assert_eq!(collector.clone().finish(), Ok([1, 2, 3]));
+        let err = collector.try_push(4).unwrap_err();
+        assert_eq!(err.into_element(), 4);
+    }
+
+    #[test]
+    fn array_collector_drop() {
+        let rc = Rc::new(());
+        let collector = ArrayCollector::<_, 3>::new()
+            .push(rc.clone())
+            .push(rc.clone());
+        assert_eq!(Rc::strong_count(&rc), 3);
+        drop(collector);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+}
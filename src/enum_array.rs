@@ -0,0 +1,146 @@
+//! Arrays indexed by the variants of a field-less enum.
+//!
+//! Collecting key-value pairs into an [`EnumArray`] fails unless every variant of the key type
+//! occurs exactly once, so the result is a total map over the enum.
+
+use crate::TryFromIterator;
+use core::{fmt, marker::PhantomData};
+
+/// A type with a fixed, small number of values, like a field-less enum.
+pub trait EnumIndex {
+    /// The number of values of the type.
+    const COUNT: usize;
+
+    /// Returns the position of the value in `0..COUNT`.
+    fn index(&self) -> usize;
+}
+
+/// An array holding a value for each of the `N` values of the key type `K`.
+///
+/// `N` must be equal to `K::COUNT`, which is checked at compile time.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EnumArray<K, V, const N: usize> {
+    values: [V; N],
+    _keys: PhantomData<fn(K)>,
+}
+
+impl<K: EnumIndex, V, const N: usize> EnumArray<K, V, N> {
+    /// Returns the value for `key`.
+    pub fn get(&self, key: &K) -> &V {
+        &self.values[key.index()]
+    }
+
+    /// Returns a mutable reference to the value for `key`.
+    pub fn get_mut(&mut self, key: &K) -> &mut V {
+        &mut self.values[key.index()]
+    }
+
+    /// Returns the values as an array in index order.
+    pub fn as_array(&self) -> &[V; N] {
+        &self.values
+    }
+
+    /// Converts into the values as an array in index order.
+    pub fn into_array(self) -> [V; N] {
+        self.values
+    }
+}
+
+/// Error returned when collecting into an [`EnumArray`] fails.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EnumArrayError<K> {
+    /// The same key occurred twice.
+    DuplicateKey(K),
+    /// No value was given for the key at this index.
+    MissingKey { index: usize },
+}
+
+impl<K: fmt::Debug> fmt::Display for EnumArrayError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateKey(key) => write!(f, "duplicate key {:?}", key),
+            Self::MissingKey { index } => write!(f, "missing key at index {}", index),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: fmt::Debug> std::error::Error for EnumArrayError<K> {}
+
+#[cfg(all(feature = "error_in_core", not(feature = "std")))]
+impl<K: fmt::Debug> core::error::Error for EnumArrayError<K> {}
+
+/// Fails on the first duplicate key, or with the first missing key once the iterator is
+/// exhausted.
+///
+/// # Panics
+///
+/// Panics if `index()` returns a value of `N` or more.
+impl<K: EnumIndex, V, const N: usize> TryFromIterator<(K, V)> for EnumArray<K, V, N> {
+    type Error = EnumArrayError<K>;
+    const EXPECTED_LEN: Option<usize> = Some(N);
+    fn try_from_iter<T>(iter: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        const { assert!(K::COUNT == N, "N doesn't match the number of keys") };
+        let mut slots: [Option<V>; N] = core::array::from_fn(|_| None);
+        for (key, val) in iter {
+            let slot = &mut slots[key.index()];
+            if slot.is_some() {
+                return Err(EnumArrayError::DuplicateKey(key));
+            }
+            *slot = Some(val);
+        }
+        if let Some(index) = slots.iter().position(Option::is_none) {
+            return Err(EnumArrayError::MissingKey { index });
+        }
+        Ok(Self {
+            values: slots.map(|slot| slot.unwrap()),
+            _keys: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EnumArray, EnumArrayError, EnumIndex};
+    use crate::TryCollect;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Color {
+        Red,
+        Green,
+        Blue,
+    }
+
+    impl EnumIndex for Color {
+        const COUNT: usize = 3;
+        fn index(&self) -> usize {
+            *self as usize
+        }
+    }
+
+    #[test]
+    fn try_collect_enum_array() {
+        use Color::*;
+        let array: EnumArray<Color, u8, 3> = [(Blue, 3), (Red, 1), (Green, 2)]
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        assert_eq!(*array.get(&Blue), 3);
+        assert_eq!(array.into_array(), [1, 2, 3]);
+        assert_eq!(
+            [(Blue, 3), (Red, 1), (Blue, 2)]
+                .into_iter()
+                .try_collect::<EnumArray<_, _, 3>>(),
+            Err(EnumArrayError::DuplicateKey(Blue))
+        );
+        assert_eq!(
+            [(Blue, 3), (Red, 1)]
+                .into_iter()
+                .try_collect::<EnumArray<_, _, 3>>(),
+            Err(EnumArrayError::MissingKey { index: 1 })
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{CapacityError, FixedCapacity, TryExtend, TryFromIterator};
+use ::arrayvec::ArrayVec;
+
+/// Fails if the iterator yields more than `CAP` elements. Shorter iterators leave the
+/// `ArrayVec` partially filled.
+impl<T, const CAP: usize> TryFromIterator<T> for ArrayVec<T, CAP> {
+    type Error = CapacityError<T>;
+    fn try_from_iter<I>(iter: I) -> Result<Self, Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut vec = ArrayVec::new();
+        vec.try_extend(iter)?;
+        Ok(vec)
+    }
+}
+
+impl<T, const CAP: usize> TryExtend<T> for ArrayVec<T, CAP> {
+    type Error = CapacityError<T>;
+    fn try_extend<I>(&mut self, iter: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        iter.into_iter().try_for_each(|val| {
+            self.try_push(val)
+                .map_err(|err| CapacityError::new(err.element(), CAP))
+        })
+    }
+}
+
+impl<T, const CAP: usize> FixedCapacity for ArrayVec<T, CAP> {
+    const CAPACITY: usize = CAP;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{TryCollect, TryExtend};
+    use arrayvec::ArrayVec;
+
+    #[test]
+    fn try_collect_arrayvec() {
+        let vec: ArrayVec<_, 3> = (1..3).try_collect().unwrap();
+        assert_eq!(vec.as_slice(), [1, 2]);
+        let err = (1..6).try_collect::<ArrayVec<_, 3>>().unwrap_err();
+        assert_eq!(*err.element(), 4);
+        assert_eq!(err.capacity(), 3);
+        let mut vec: ArrayVec<_, 3> = (1..3).try_collect().unwrap();
+        assert_eq!(vec.try_extend(3..5).unwrap_err().into_element(), 4);
+        assert_eq!(vec.as_slice(), [1, 2, 3]);
+    }
+}
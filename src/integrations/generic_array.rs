@@ -0,0 +1,121 @@
+use crate::{check_size_hint, NonMatchingLenError, TryFromIterator};
+use ::generic_array::{ArrayLength, GenericArray};
+use core::mem::MaybeUninit;
+
+/// Fails if the iterator doesn't yield exactly `N` elements.
+///
+/// If the collection fails, the elements consumed so far are dropped, just like for arrays.
+impl<T, N: ArrayLength> TryFromIterator<T> for GenericArray<T, N> {
+    type Error = NonMatchingLenError;
+    const EXPECTED_LEN: Option<usize> = Some(N::USIZE);
+    fn try_from_iter<I>(iter: I) -> Result<Self, Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+        check_size_hint(N::USIZE, iter.size_hint())?;
+        let mut array = GenericArray::<T, N>::uninit();
+        let mut prefix = InitPrefix {
+            slots: array.as_mut_slice(),
+            len: 0,
+        };
+        while prefix.len < N::USIZE {
+            match iter.next() {
+                Some(val) => {
+                    prefix.slots[prefix.len].write(val);
+                    prefix.len += 1;
+                }
+                None => {
+                    return Err(NonMatchingLenError::TooShort {
+                        expected: N::USIZE,
+                        actual: prefix.len,
+                        consumed: prefix.len,
+                    });
+                }
+            }
+        }
+        if iter.next().is_some() {
+            return Err(NonMatchingLenError::TooLong {
+                expected: N::USIZE,
+                actual: N::USIZE + 1,
+                consumed: N::USIZE + 1,
+            });
+        }
+        core::mem::forget(prefix);
+        // All `N` slots have been written, and forgetting `prefix` handed them to `array`.
+        Ok(unsafe { GenericArray::assume_init(array) })
+    }
+}
+
+/// The initialized prefix of a slice of slots, which is dropped unless it is forgotten.
+struct InitPrefix<'a, T> {
+    slots: &'a mut [MaybeUninit<T>],
+    len: usize,
+}
+
+impl<T> Drop for InitPrefix<'_, T> {
+    fn drop(&mut self) {
+        for slot in &mut self.slots[..self.len] {
+            // The first `len` slots are initialized.
+            unsafe { slot.assume_init_drop() };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use crate::{NonMatchingLenError, TryCollect};
+    use generic_array::typenum::{U3, U4};
+    use generic_array::GenericArray;
+    use std::rc::Rc;
+
+    #[test]
+    fn try_collect_generic_array() {
+        let array: GenericArray<_, U3> = (1..4).try_collect().unwrap();
+        assert_eq!(array.as_slice(), [1, 2, 3]);
+        assert_eq!(
+            (1..5).filter(|_| true).try_collect::<GenericArray<_, U3>>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 3,
+                actual: 4,
+                consumed: 4
+            })
+        );
+        assert_eq!(
+            (1..3)
+                .chain(core::iter::from_fn(|| None))
+                .try_collect::<GenericArray<_, U3>>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 3,
+                actual: 2,
+                consumed: 2
+            })
+        );
+        assert_eq!(
+            (1..3).try_collect::<GenericArray<_, U3>>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 3,
+                actual: 2,
+                consumed: 0
+            })
+        );
+    }
+
+    #[test]
+    fn try_collect_generic_array_drop() {
+        let rc = Rc::new(());
+        let result = core::iter::repeat_with(|| rc.clone())
+            .take(3)
+            .chain(core::iter::from_fn(|| None))
+            .try_collect::<GenericArray<_, U4>>();
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+        let result = core::iter::repeat_with(|| rc.clone())
+            .take(5)
+            .filter(|_| true)
+            .try_collect::<GenericArray<_, U4>>();
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{CapacityError, FixedCapacity, TryExtend, TryFromIterator};
+use ::heapless::{LenType, String, Vec};
+
+/// Fails if the iterator yields more than `N` elements. Shorter iterators leave the `Vec`
+/// partially filled.
+impl<T, LenT: LenType, const N: usize> TryFromIterator<T> for Vec<T, N, LenT> {
+    type Error = CapacityError<T>;
+    fn try_from_iter<I>(iter: I) -> Result<Self, Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut vec = Vec::new();
+        vec.try_extend(iter)?;
+        Ok(vec)
+    }
+}
+
+impl<T, LenT: LenType, const N: usize> TryExtend<T> for Vec<T, N, LenT> {
+    type Error = CapacityError<T>;
+    fn try_extend<I>(&mut self, iter: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        iter.into_iter()
+            .try_for_each(|val| self.push(val).map_err(|val| CapacityError::new(val, N)))
+    }
+}
+
+impl<T, LenT: LenType, const N: usize> FixedCapacity for Vec<T, N, LenT> {
+    const CAPACITY: usize = N;
+}
+
+/// Fails if the chars don't fit into `N` bytes. Shorter iterators leave the `String` partially
+/// filled.
+impl<LenT: LenType, const N: usize> TryFromIterator<char> for String<N, LenT> {
+    type Error = CapacityError<char>;
+    fn try_from_iter<I>(iter: I) -> Result<Self, Self::Error>
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let mut string = String::new();
+        string.try_extend(iter)?;
+        Ok(string)
+    }
+}
+
+impl<LenT: LenType, const N: usize> TryExtend<char> for String<N, LenT> {
+    type Error = CapacityError<char>;
+    fn try_extend<I>(&mut self, iter: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = char>,
+    {
+        iter.into_iter()
+            .try_for_each(|c| self.push(c).map_err(|_| CapacityError::new(c, N)))
+    }
+}
+
+/// The capacity is measured in bytes.
+impl<LenT: LenType, const N: usize> FixedCapacity for String<N, LenT> {
+    const CAPACITY: usize = N;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{TryCollect, TryExtend};
+    use heapless::{String, Vec};
+
+    #[test]
+    fn try_collect_heapless_vec() {
+        let vec: Vec<_, 3> = (1..3).try_collect().unwrap();
+        assert_eq!(vec, [1, 2]);
+        let err = (1..6).try_collect::<Vec<_, 3>>().unwrap_err();
+        assert_eq!(*err.element(), 4);
+        assert_eq!(err.capacity(), 3);
+        let mut vec: Vec<_, 3, u8> = (1..3).try_collect().unwrap();
+        assert_eq!(vec.try_extend(3..5).unwrap_err().into_element(), 4);
+        assert_eq!(vec, [1, 2, 3]);
+    }
+
+    #[test]
+    fn try_collect_heapless_string() {
+        let string: String<5> = "aäö".chars().try_collect().unwrap();
+        assert_eq!(string, "aäö");
+        let err = "aäöü".chars().try_collect::<String<6>>().unwrap_err();
+        assert_eq!(*err.element(), 'ü');
+        assert_eq!(err.capacity(), 6);
+        let mut string: String<4, u8> = "ab".chars().try_collect().unwrap();
+        assert_eq!(
+            string.try_extend("cäd".chars()).unwrap_err().into_element(),
+            'ä'
+        );
+        assert_eq!(string, "abc");
+    }
+}
@@ -0,0 +1,12 @@
+//! Implementations of the traits of this crate for collections from other crates.
+
+#[cfg(feature = "arrayvec")]
+mod arrayvec;
+#[cfg(feature = "generic-array")]
+mod generic_array;
+#[cfg(feature = "heapless")]
+mod heapless;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+#[cfg(feature = "tinyvec")]
+mod tinyvec;
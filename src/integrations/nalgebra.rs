@@ -0,0 +1,43 @@
+use crate::{NonMatchingLenError, TryFromIterator};
+use ::nalgebra::{SVector, Scalar};
+
+/// Fails if the iterator doesn't yield exactly `D` elements.
+impl<T: Scalar, const D: usize> TryFromIterator<T> for SVector<T, D> {
+    type Error = NonMatchingLenError;
+    const EXPECTED_LEN: Option<usize> = Some(D);
+    #[inline]
+    fn try_from_iter<I>(iter: I) -> Result<Self, Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        <[T; D]>::try_from_iter(iter).map(SVector::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{NonMatchingLenError, TryCollect};
+    use nalgebra::{SVector, Vector3};
+
+    #[test]
+    fn try_collect_svector() {
+        let vec: Vector3<f32> = [1.0, 2.0, 3.0].into_iter().try_collect().unwrap();
+        assert_eq!(vec, Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(
+            (0..4).filter(|_| true).try_collect::<SVector<i32, 3>>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 3,
+                actual: 4,
+                consumed: 4
+            })
+        );
+        assert_eq!(
+            (0..2).try_collect::<SVector<i32, 3>>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 3,
+                actual: 2,
+                consumed: 0
+            })
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{CapacityError, FixedCapacity, TryExtend, TryFromIterator};
+use ::tinyvec::ArrayVec;
+
+/// Fails if the iterator yields more than `N` elements. Shorter iterators leave the `ArrayVec`
+/// partially filled.
+///
+/// `tinyvec` fills the unused slots with default values, so `T` needs to implement `Default`.
+impl<T: Default, const N: usize> TryFromIterator<T> for ArrayVec<[T; N]> {
+    type Error = CapacityError<T>;
+    fn try_from_iter<I>(iter: I) -> Result<Self, Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut vec = ArrayVec::new();
+        vec.try_extend(iter)?;
+        Ok(vec)
+    }
+}
+
+impl<T: Default, const N: usize> TryExtend<T> for ArrayVec<[T; N]> {
+    type Error = CapacityError<T>;
+    fn try_extend<I>(&mut self, iter: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        iter.into_iter()
+            .try_for_each(|val| match self.try_push(val) {
+                None => Ok(()),
+                Some(val) => Err(CapacityError::new(val, N)),
+            })
+    }
+}
+
+impl<T: Default, const N: usize> FixedCapacity for ArrayVec<[T; N]> {
+    const CAPACITY: usize = N;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{TryCollect, TryExtend};
+    use tinyvec::ArrayVec;
+
+    #[test]
+    fn try_collect_tinyvec() {
+        let vec: ArrayVec<[_; 3]> = (1..3).try_collect().unwrap();
+        assert_eq!(vec.as_slice(), [1, 2]);
+        let err = (1..6).try_collect::<ArrayVec<[_; 3]>>().unwrap_err();
+        assert_eq!(*err.element(), 4);
+        assert_eq!(err.capacity(), 3);
+        let mut vec: ArrayVec<[_; 3]> = (1..3).try_collect().unwrap();
+        assert_eq!(vec.try_extend(3..5).unwrap_err().into_element(), 4);
+        assert_eq!(vec.as_slice(), [1, 2, 3]);
+    }
+}
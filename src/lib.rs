@@ -1,108 +1,1852 @@
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "trusted_len", feature(trusted_len, min_specialization))]
+#![cfg_attr(feature = "array_assume_init", feature(maybe_uninit_array_assume_init))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 use core::fmt;
 
+pub mod adapters;
+pub mod bit_array;
+#[cfg(feature = "alloc")]
+pub mod bounded;
+pub mod collector;
+#[cfg(feature = "enum_array")]
+pub mod enum_array;
+mod integrations;
+mod macros;
+#[cfg(all(test, feature = "alloc"))]
+mod miri_tests;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+pub mod partial_array;
+#[cfg(feature = "serde")]
+pub mod serde_array;
+#[cfg(feature = "futures")]
+pub mod stream;
+#[cfg(feature = "trusted_len")]
+mod trusted_len;
+#[cfg(feature = "alloc")]
+pub mod unique;
+
+#[cfg(not(feature = "trusted_len"))]
+use extend_exact as spec_extend_exact;
+pub use macros::NoneError;
+use partial_array::PartialArray;
+#[cfg(feature = "trusted_len")]
+use trusted_len::spec_extend_exact;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::macros::IntoResult;
+}
+
 pub trait TryFromIterator<A>: Sized {
     type Error;
 
+    /// The exact number of elements the target needs, if it is fixed.
+    ///
+    /// Generic code can use this to check the size hint of an iterator up front.
+    const EXPECTED_LEN: Option<usize> = None;
+
+    fn try_from_iter<T>(iter: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator<Item = A>;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NonMatchingLenError {
+    /// The iterator yielded fewer elements than the target can hold.
+    TooShort {
+        expected: usize,
+        actual: usize,
+        consumed: usize,
+    },
+    /// The iterator yielded more elements than the target can hold.
+    ///
+    /// The iterator is not drained past the first surplus element, so `actual` is only a lower
+    /// bound for the length of the iterator.
+    TooLong {
+        expected: usize,
+        actual: usize,
+        consumed: usize,
+    },
+}
+
+impl NonMatchingLenError {
+    /// The number of elements the target needed.
+    pub fn expected(&self) -> usize {
+        match *self {
+            Self::TooShort { expected, .. } | Self::TooLong { expected, .. } => expected,
+        }
+    }
+
+    /// The number of elements the iterator yielded, or a lower bound in the `TooLong` case.
+    ///
+    /// If the mismatch was detected from the size hint of the iterator without consuming it, this
+    /// is the bound reported by the iterator instead.
+    pub fn actual(&self) -> usize {
+        match *self {
+            Self::TooShort { actual, .. } | Self::TooLong { actual, .. } => actual,
+        }
+    }
+
+    /// The number of elements consumed from the iterator before the mismatch was detected.
+    ///
+    /// This is how far the iterator has been advanced, which matters when collecting through a
+    /// mutable reference. It is zero if the mismatch was detected from the size hint.
+    pub fn consumed(&self) -> usize {
+        match *self {
+            Self::TooShort { consumed, .. } | Self::TooLong { consumed, .. } => consumed,
+        }
+    }
+}
+
+impl fmt::Display for NonMatchingLenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort {
+                expected, actual, ..
+            } => {
+                write!(f, "expected {} elements, found {}", expected, actual)
+            }
+            Self::TooLong {
+                expected, actual, ..
+            } => {
+                write!(
+                    f,
+                    "expected {} elements, found at least {}",
+                    expected, actual
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NonMatchingLenError {}
+
+// `std::error::Error` is a re-export of `core::error::Error` on Rust 1.81 and newer, so only one of
+// the two impls may be active.
+#[cfg(all(feature = "error_in_core", not(feature = "std")))]
+impl core::error::Error for NonMatchingLenError {}
+
+/// Converts into an I/O error of kind [`InvalidData`](std::io::ErrorKind::InvalidData), so that
+/// `try_collect()?` works in functions returning `std::io::Result`.
+///
+/// No conversion is needed for `anyhow::Error`, since `anyhow` converts any type implementing
+/// `std::error::Error + Send + Sync + 'static`.
+#[cfg(feature = "std")]
+impl From<NonMatchingLenError> for std::io::Error {
+    fn from(err: NonMatchingLenError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err)
+    }
+}
+
+/// Error returned by [`TryCollect::try_collect_recoverable()`], holding the consumed elements.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RecoverableLenError<A> {
+    error: NonMatchingLenError,
+    elements: alloc::vec::Vec<A>,
+}
+
+#[cfg(feature = "alloc")]
+impl<A> RecoverableLenError<A> {
+    /// The underlying length mismatch.
+    pub fn error(&self) -> NonMatchingLenError {
+        self.error
+    }
+
+    /// The elements consumed from the iterator, in iteration order.
+    pub fn elements(&self) -> &[A] {
+        &self.elements
+    }
+
+    /// Returns the elements consumed from the iterator, in iteration order.
+    ///
+    /// If the iterator was too long, this includes the first surplus element.
+    pub fn into_elements(self) -> alloc::vec::Vec<A> {
+        self.elements
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A> fmt::Display for RecoverableLenError<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A: fmt::Debug> std::error::Error for RecoverableLenError<A> {}
+
+#[cfg(all(feature = "alloc", feature = "error_in_core", not(feature = "std")))]
+impl<A: fmt::Debug> core::error::Error for RecoverableLenError<A> {}
+
+/// Error returned by [`TryCollect::try_collect_ok()`] and [`TryCollect::try_map_collect()`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CollectError<E> {
+    /// The iterator yielded an `Err`.
+    Item(E),
+    /// The iterator yielded the wrong number of elements.
+    Length(NonMatchingLenError),
+}
+
+impl<E> From<NonMatchingLenError> for CollectError<E> {
+    fn from(err: NonMatchingLenError) -> Self {
+        Self::Length(err)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for CollectError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Item(err) => err.fmt(f),
+            Self::Length(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Debug + fmt::Display> std::error::Error for CollectError<E> {}
+
+#[cfg(all(feature = "error_in_core", not(feature = "std")))]
+impl<E: fmt::Debug + fmt::Display> core::error::Error for CollectError<E> {}
+
+/// Error returned by [`TryCollect::try_collect_diagnostic()`], including the type name of the
+/// iterator in its message.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DiagnosticError<E> {
+    error: E,
+    iter_type: &'static str,
+}
+
+impl<E> DiagnosticError<E> {
+    /// Returns the error returned by the target type.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Converts into the error returned by the target type.
+    pub fn into_error(self) -> E {
+        self.error
+    }
+
+    /// Returns the type name of the iterator, as returned by [`core::any::type_name()`].
+    pub fn iter_type(&self) -> &'static str {
+        self.iter_type
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for DiagnosticError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (collecting from `{}`)", self.error, self.iter_type)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Debug + fmt::Display> std::error::Error for DiagnosticError<E> {}
+
+#[cfg(all(feature = "error_in_core", not(feature = "std")))]
+impl<E: fmt::Debug + fmt::Display> core::error::Error for DiagnosticError<E> {}
+
+/// Error returned by [`TryCollect::try_collect_with_extra()`], holding the first surplus element
+/// if the iterator was too long.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ExtraElementError<A> {
+    /// The iterator yielded fewer elements than the target can hold.
+    TooShort { expected: usize, actual: usize },
+    /// The iterator yielded more elements than the target can hold, starting with `extra`.
+    TooLong { expected: usize, extra: A },
+}
+
+impl<A> ExtraElementError<A> {
+    /// Returns the first surplus element, or `None` if the iterator was too short.
+    pub fn into_extra(self) -> Option<A> {
+        match self {
+            Self::TooShort { .. } => None,
+            Self::TooLong { extra, .. } => Some(extra),
+        }
+    }
+
+    /// Converts into the corresponding length error, dropping the surplus element.
+    pub fn into_len_error(self) -> NonMatchingLenError {
+        match self {
+            Self::TooShort { expected, actual } => NonMatchingLenError::TooShort {
+                expected,
+                actual,
+                consumed: actual,
+            },
+            Self::TooLong { expected, .. } => NonMatchingLenError::TooLong {
+                expected,
+                actual: expected + 1,
+                consumed: expected + 1,
+            },
+        }
+    }
+}
+
+impl<A> fmt::Display for ExtraElementError<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort { expected, actual } => {
+                write!(f, "expected {} elements, found {}", expected, actual)
+            }
+            Self::TooLong { expected, .. } => {
+                write!(f, "expected {} elements, found more", expected)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A: fmt::Debug> std::error::Error for ExtraElementError<A> {}
+
+#[cfg(all(feature = "error_in_core", not(feature = "std")))]
+impl<A: fmt::Debug> core::error::Error for ExtraElementError<A> {}
+
+/// Error returned by [`TryCollect::try_collect_bounded()`] when the iterator exceeds the limit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LengthExceeded {
+    limit: usize,
+}
+
+impl LengthExceeded {
+    /// The maximum number of elements.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+impl fmt::Display for LengthExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "more than {} elements", self.limit)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthExceeded {}
+
+#[cfg(all(feature = "error_in_core", not(feature = "std")))]
+impl core::error::Error for LengthExceeded {}
+
+/// Error returned by [`TryCollect::try_collect_validated()`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ValidateError {
+    /// The element at `index` failed validation.
+    Invalid { index: usize },
+    /// The iterator yielded the wrong number of elements.
+    Length(NonMatchingLenError),
+}
+
+impl From<NonMatchingLenError> for ValidateError {
+    fn from(err: NonMatchingLenError) -> Self {
+        Self::Length(err)
+    }
+}
+
+impl fmt::Display for ValidateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid { index } => write!(f, "element at index {} failed validation", index),
+            Self::Length(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValidateError {}
+
+#[cfg(all(feature = "error_in_core", not(feature = "std")))]
+impl core::error::Error for ValidateError {}
+
+/// Error returned by [`TryCollect::try_collect_until()`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SentinelError {
+    /// The iterator ended after `consumed` elements without yielding a sentinel.
+    MissingSentinel { consumed: usize },
+    /// The wrong number of elements came before the sentinel.
+    Length(NonMatchingLenError),
+}
+
+impl From<NonMatchingLenError> for SentinelError {
+    fn from(err: NonMatchingLenError) -> Self {
+        Self::Length(err)
+    }
+}
+
+impl fmt::Display for SentinelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSentinel { consumed } => {
+                write!(f, "no sentinel after {} elements", consumed)
+            }
+            Self::Length(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SentinelError {}
+
+#[cfg(all(feature = "error_in_core", not(feature = "std")))]
+impl core::error::Error for SentinelError {}
+
+/// Error returned by [`TryCollect::try_collect_dedup()`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DedupError {
+    /// The element at `index` is equal to the element before it.
+    Duplicate { index: usize },
+    /// The iterator yielded the wrong number of elements.
+    Length(NonMatchingLenError),
+}
+
+impl From<NonMatchingLenError> for DedupError {
+    fn from(err: NonMatchingLenError) -> Self {
+        Self::Length(err)
+    }
+}
+
+impl fmt::Display for DedupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Duplicate { index } => {
+                write!(f, "element at index {} equals the previous element", index)
+            }
+            Self::Length(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DedupError {}
+
+#[cfg(all(feature = "error_in_core", not(feature = "std")))]
+impl core::error::Error for DedupError {}
+
+/// Error returned when a bounded collection overflows, holding the element that didn't fit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CapacityError<A> {
+    element: A,
+    capacity: usize,
+}
+
+impl<A> CapacityError<A> {
+    /// Creates an error for `element` not fitting into a collection of the given capacity.
+    pub fn new(element: A, capacity: usize) -> Self {
+        Self { element, capacity }
+    }
+
+    /// The element that didn't fit.
+    pub fn element(&self) -> &A {
+        &self.element
+    }
+
+    /// Returns the element that didn't fit.
+    pub fn into_element(self) -> A {
+        self.element
+    }
+
+    /// The capacity of the collection.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl<A> fmt::Display for CapacityError<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "capacity of {} exceeded", self.capacity)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A: fmt::Debug> std::error::Error for CapacityError<A> {}
+
+#[cfg(all(feature = "error_in_core", not(feature = "std")))]
+impl<A: fmt::Debug> core::error::Error for CapacityError<A> {}
+
+/// Fails unless the iterator yields exactly `N` elements.
+///
+/// The iterator is never polled again after it returned `None`, so an iterator that isn't fused
+/// is treated as ending at its first `None`.
+impl<A, const N: usize> TryFromIterator<A> for [A; N] {
+    type Error = NonMatchingLenError;
+    const EXPECTED_LEN: Option<usize> = Some(N);
+    #[inline]
     fn try_from_iter<T>(iter: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator<Item = A>,
+    {
+        if N == 0 {
+            // Handle empty arrays without going through `PartialArray`. They are trivially
+            // constructed, so there is no need for any unsafe code.
+            let mut iter = iter.into_iter();
+            check_size_hint(0, iter.size_hint())?;
+            if iter.next().is_some() {
+                return Err(NonMatchingLenError::TooLong {
+                    expected: 0,
+                    actual: 1,
+                    consumed: 1,
+                });
+            }
+            return Ok(core::array::from_fn(|_| unreachable!()));
+        }
+        let mut partial = PartialArray::<A, N>::new();
+        partial.try_extend_exact(iter)?;
+        Ok(partial.into_array())
+    }
+}
+
+/// Collects an iterator into an array of exactly `N` elements.
+///
+/// This is a shorthand for `<[A; N]>::try_from_iter(iter)`. The length can be given explicitly,
+///
+/// ```
+/// use try_collect::array_from_iter;
+///
+/// assert_eq!(array_from_iter::<_, _, 3>(1..4), Ok([1, 2, 3]));
+/// assert!(array_from_iter::<_, _, 3>(1..5).is_err());
+/// ```
+///
+/// or be inferred from the context:
+///
+/// ```
+/// # use try_collect::array_from_iter;
+/// let [x, y] = array_from_iter("1,2".split(',')).unwrap();
+/// assert_eq!((x, y), ("1", "2"));
+/// ```
+#[inline]
+pub fn array_from_iter<A, I, const N: usize>(iter: I) -> Result<[A; N], NonMatchingLenError>
+where
+    I: IntoIterator<Item = A>,
+{
+    <[A; N]>::try_from_iter(iter)
+}
+
+/// Converts an array of length `M` into an array of length `N`, failing if the lengths differ.
+///
+/// This is a `const fn`, so it can be used to check array lengths at compile time:
+///
+/// ```
+/// use try_collect::try_from_array_const;
+///
+/// const ARRAY: [u8; 3] = match try_from_array_const([1, 2, 3]) {
+///     Ok(array) => array,
+///     Err(_) => panic!("length mismatch"),
+/// };
+/// assert_eq!(ARRAY, [1, 2, 3]);
+/// assert!(try_from_array_const::<_, 2, 3>([1, 2]).is_err());
+/// ```
+pub const fn try_from_array_const<A: Copy, const M: usize, const N: usize>(
+    src: [A; M],
+) -> Result<[A; N], NonMatchingLenError> {
+    if M < N {
+        return Err(NonMatchingLenError::TooShort {
+            expected: N,
+            actual: M,
+            consumed: 0,
+        });
+    }
+    if M > N {
+        return Err(NonMatchingLenError::TooLong {
+            expected: N,
+            actual: M,
+            consumed: 0,
+        });
+    }
+    // SAFETY: `M == N`, so `[A; M]` and `[A; N]` are the same type.
+    Ok(unsafe { (&src as *const [A; M] as *const [A; N]).read() })
+}
+
+/// Overwrites the elements of `slice` with the elements of an iterator of exactly the same length.
+///
+/// On error, the elements the iterator yielded have already been written to the start of the
+/// slice, unless the size hint of the iterator ruled out a match up front.
+///
+/// ```
+/// let mut buf = [0; 3];
+/// try_collect::try_fill_slice(&mut buf, 1..4).unwrap();
+/// assert_eq!(buf, [1, 2, 3]);
+/// assert!(try_collect::try_fill_slice(&mut buf[1..], 1..4).is_err());
+/// ```
+#[inline]
+pub fn try_fill_slice<T, I>(slice: &mut [T], iter: I) -> Result<(), NonMatchingLenError>
+where
+    I: IntoIterator<Item = T>,
+{
+    let expected = slice.len();
+    let mut iter = iter.into_iter();
+    check_size_hint(expected, iter.size_hint())?;
+    for (actual, slot) in slice.iter_mut().enumerate() {
+        match iter.next() {
+            Some(val) => *slot = val,
+            None => {
+                return Err(NonMatchingLenError::TooShort {
+                    expected,
+                    actual,
+                    consumed: actual,
+                });
+            }
+        }
+    }
+    if iter.next().is_some() {
+        return Err(NonMatchingLenError::TooLong {
+            expected,
+            actual: expected + 1,
+            consumed: expected + 1,
+        });
+    }
+    Ok(())
+}
+
+/// Collects an iterator into a boxed slice of exactly `expected_len` elements.
+///
+/// This is the counterpart of collecting into `Box<[A; N]>` for lengths only known at runtime.
+/// The buffer is allocated with the expected length up front.
+///
+/// ```
+/// let boxed = try_collect::boxed_slice_from_iter(3, 1..4).unwrap();
+/// assert_eq!(*boxed, [1, 2, 3]);
+/// assert!(try_collect::boxed_slice_from_iter(2, 1..4).is_err());
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn boxed_slice_from_iter<T, I>(
+    expected_len: usize,
+    iter: I,
+) -> Result<alloc::boxed::Box<[T]>, NonMatchingLenError>
+where
+    I: IntoIterator<Item = T>,
+{
+    let iter = iter.into_iter();
+    check_size_hint(expected_len, iter.size_hint())?;
+    let mut vec = alloc::vec::Vec::with_capacity(expected_len);
+    for val in iter {
+        if vec.len() == expected_len {
+            return Err(NonMatchingLenError::TooLong {
+                expected: expected_len,
+                actual: expected_len + 1,
+                consumed: expected_len + 1,
+            });
+        }
+        vec.push(val);
+    }
+    if vec.len() < expected_len {
+        return Err(NonMatchingLenError::TooShort {
+            expected: expected_len,
+            actual: vec.len(),
+            consumed: vec.len(),
+        });
+    }
+    Ok(vec.into_boxed_slice())
+}
+
+/// Moves the elements of `slice` into an array, leaving `T::default()` in their place.
+///
+/// Fails without modifying the slice if it doesn't have exactly `N` elements.
+///
+/// ```
+/// let mut names = vec![String::from("a"), String::from("b")];
+/// let [a, b] = try_collect::try_collect_taken(&mut names).unwrap();
+/// assert_eq!((a.as_str(), b.as_str()), ("a", "b"));
+/// assert_eq!(names, ["", ""]);
+/// assert!(try_collect::try_collect_taken::<3, _>(&mut names).is_err());
+/// ```
+pub fn try_collect_taken<const N: usize, T: Default>(
+    slice: &mut [T],
+) -> Result<[T; N], NonMatchingLenError> {
+    check_size_hint(N, (slice.len(), Some(slice.len())))?;
+    slice.iter_mut().map(core::mem::take).try_collect()
+}
+
+/// Reads exactly `N` bytes from `reader` into an array.
+///
+/// Fails with an error of kind `UnexpectedEof` if the reader runs out of data first. Only `N`
+/// bytes are read, so any further bytes remain in the reader. The array is zero-initialized
+/// before reading, since `Read` impls may legally read from the buffer they are passed.
+///
+/// ```
+/// let mut reader: &[u8] = b"abcde";
+/// assert_eq!(try_collect::read_array(&mut reader).unwrap(), *b"abc");
+/// assert_eq!(reader, b"de");
+/// assert!(try_collect::read_array::<3, _>(&mut reader).is_err());
+/// ```
+#[cfg(feature = "std")]
+pub fn read_array<const N: usize, R: std::io::Read>(reader: &mut R) -> std::io::Result<[u8; N]> {
+    let mut array = [0; N];
+    reader.read_exact(&mut array)?;
+    Ok(array)
+}
+
+/// A collection with a capacity fixed at compile time.
+pub trait FixedCapacity {
+    /// The maximum number of elements, or for arrays the exact number.
+    const CAPACITY: usize;
+}
+
+impl<A, const N: usize> FixedCapacity for [A; N] {
+    const CAPACITY: usize = N;
+}
+
+impl<A, const N: usize> FixedCapacity for PartialArray<A, N> {
+    const CAPACITY: usize = N;
+}
+
+/// Appends the elements of an iterator to a collection with limited capacity.
+///
+/// Unlike [`TryFromIterator`], this allows building a collection incrementally from several
+/// iterators.
+pub trait TryExtend<A> {
+    type Error;
+
+    /// Appends the elements of `iter`, failing if they don't fit.
+    ///
+    /// On overflow, the collection keeps the elements it managed to add.
+    fn try_extend<T>(&mut self, iter: T) -> Result<(), Self::Error>
     where
         T: IntoIterator<Item = A>;
 }
 
-#[derive(Copy, Clone, Debug)]
-pub struct NonMatchingLenError;
+/// Fails with the first element that doesn't fit once the array is full.
+impl<A, const N: usize> TryExtend<A> for PartialArray<A, N> {
+    type Error = CapacityError<A>;
+    #[inline]
+    fn try_extend<T>(&mut self, iter: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = A>,
+    {
+        for val in iter {
+            if self.full() {
+                return Err(CapacityError::new(val, N));
+            }
+            self.push(val);
+        }
+        Ok(())
+    }
+}
+
+/// Fills the remaining capacity of an existing collection from an iterator.
+pub trait TryExtendExact<A> {
+    type Error;
+
+    /// Appends the elements of `iter`, which must exactly fill the remaining capacity.
+    fn try_extend_exact<T>(&mut self, iter: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = A>;
+}
+
+/// Tops up a partially filled array.
+///
+/// If the iterator is too short, the array keeps the elements it yielded. If the iterator is too
+/// long, the array is full and the first surplus element is dropped. If the size hint of the
+/// iterator already rules out a match, the array is left unchanged.
+impl<A, const N: usize> TryExtendExact<A> for PartialArray<A, N> {
+    type Error = NonMatchingLenError;
+    #[inline]
+    fn try_extend_exact<T>(&mut self, iter: T) -> Result<(), Self::Error>
+    where
+        T: IntoIterator<Item = A>,
+    {
+        let iter = iter.into_iter();
+        check_size_hint(self.remaining(), iter.size_hint())?;
+        spec_extend_exact(self, iter)
+    }
+}
+
+/// Fills the remaining capacity of `partial`, assuming the size hint has already been checked.
+///
+/// The iterator is driven by `try_fold()` rather than `next()`, since adapters like `Chain` or
+/// `FlatMap` implement it much more efficiently.
+#[inline]
+fn extend_exact<A, I, const N: usize>(
+    partial: &mut PartialArray<A, N>,
+    iter: I,
+) -> Result<(), NonMatchingLenError>
+where
+    I: Iterator<Item = A>,
+{
+    let start = partial.len();
+    let expected = N - start;
+    if partial.fill_from(iter) {
+        return Err(NonMatchingLenError::TooLong {
+            expected,
+            actual: expected + 1,
+            consumed: expected + 1,
+        });
+    }
+    if !partial.full() {
+        return Err(NonMatchingLenError::TooShort {
+            expected,
+            actual: partial.len() - start,
+            consumed: partial.len() - start,
+        });
+    }
+    Ok(())
+}
+
+/// Fails early if the size hint of an iterator rules out that it yields exactly `expected`
+/// elements.
+///
+/// Only the bounds themselves are trusted, so an imprecise hint never causes an error.
+#[inline]
+fn check_size_hint(
+    expected: usize,
+    (lower, upper): (usize, Option<usize>),
+) -> Result<(), NonMatchingLenError> {
+    if lower > expected {
+        return Err(NonMatchingLenError::TooLong {
+            expected,
+            actual: lower,
+            consumed: 0,
+        });
+    }
+    match upper {
+        Some(upper) if upper < expected => Err(NonMatchingLenError::TooShort {
+            expected,
+            actual: upper,
+            consumed: 0,
+        }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A, const N: usize> TryFromIterator<A> for alloc::boxed::Box<[A; N]> {
+    type Error = NonMatchingLenError;
+    const EXPECTED_LEN: Option<usize> = Some(N);
+    #[inline]
+    fn try_from_iter<T>(iter: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator<Item = A>,
+    {
+        let iter = iter.into_iter();
+        check_size_hint(N, iter.size_hint())?;
+        // Fill the heap allocation in place, so the array never lives on the stack.
+        let mut partial = partial_box::PartialBox::<A, N>::new();
+        for val in iter {
+            if partial.full() {
+                return Err(NonMatchingLenError::TooLong {
+                    expected: N,
+                    actual: N + 1,
+                    consumed: N + 1,
+                });
+            }
+            partial.push(val);
+        }
+        if !partial.full() {
+            return Err(NonMatchingLenError::TooShort {
+                expected: N,
+                actual: partial.len(),
+                consumed: partial.len(),
+            });
+        }
+        Ok(partial.into_box())
+    }
+}
+
+/// Succeeds if and only if the iterator is empty.
+impl<A> TryFromIterator<A> for () {
+    type Error = NonMatchingLenError;
+    const EXPECTED_LEN: Option<usize> = Some(0);
+    #[inline]
+    fn try_from_iter<T>(iter: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator<Item = A>,
+    {
+        let [] = <[A; 0]>::try_from_iter(iter)?;
+        Ok(())
+    }
+}
+
+macro_rules! tuple_impls {
+    ($($len:literal => ($($name:ident)+))+) => {
+        $(
+            impl<A> TryFromIterator<A> for ($(tuple_impls!(@elem $name A),)+) {
+                type Error = NonMatchingLenError;
+                const EXPECTED_LEN: Option<usize> = Some($len);
+                #[inline]
+                fn try_from_iter<T>(iter: T) -> Result<Self, Self::Error>
+                where
+                    T: IntoIterator<Item = A>,
+                {
+                    let [$($name),+] = <[A; $len]>::try_from_iter(iter)?;
+                    Ok(($($name,)+))
+                }
+            }
+        )+
+    };
+    (@elem $name:ident $ty:ident) => { $ty };
+}
+
+tuple_impls! {
+    1 => (a)
+    2 => (a b)
+    3 => (a b c)
+    4 => (a b c d)
+    5 => (a b c d e)
+    6 => (a b c d e f)
+    7 => (a b c d e f g)
+    8 => (a b c d e f g h)
+    9 => (a b c d e f g h i)
+    10 => (a b c d e f g h i j)
+    11 => (a b c d e f g h i j k)
+    12 => (a b c d e f g h i j k l)
+}
+
+/// An iterator over elements stored contiguously in memory.
+pub trait ContiguousIterator: Iterator {
+    /// The type of the stored elements.
+    type Element;
+
+    /// Returns the elements that haven't been yielded yet.
+    fn as_slice(&self) -> &[Self::Element];
+}
+
+impl<'a, A> ContiguousIterator for core::slice::Iter<'a, A> {
+    type Element = A;
+    #[inline]
+    fn as_slice(&self) -> &[A] {
+        core::slice::Iter::as_slice(self)
+    }
+}
+
+impl<A, const N: usize> ContiguousIterator for core::array::IntoIter<A, N> {
+    type Element = A;
+    #[inline]
+    fn as_slice(&self) -> &[A] {
+        core::array::IntoIter::as_slice(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A> ContiguousIterator for alloc::vec::IntoIter<A> {
+    type Element = A;
+    #[inline]
+    fn as_slice(&self) -> &[A] {
+        alloc::vec::IntoIter::as_slice(self)
+    }
+}
+
+/// The order in which [`TryCollect::try_collect_matrix()`] places the elements of a flat iterator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Order {
+    /// Fill one row after the other, so element `k` goes to `[k / COLS][k % COLS]`.
+    RowMajor,
+    /// Fill one column after the other, so element `k` goes to `[k % ROWS][k / ROWS]`.
+    ColumnMajor,
+}
+
+pub trait TryCollect: Iterator {
+    #[inline]
+    fn try_collect<B>(self) -> Result<B, B::Error>
+    where
+        B: TryFromIterator<Self::Item>,
+        Self: Sized,
+    {
+        TryFromIterator::try_from_iter(self)
+    }
+
+    /// Collects into an array of `N` elements, like `try_collect()` with only the length
+    /// specified.
+    ///
+    /// ```
+    /// use try_collect::TryCollect;
+    ///
+    /// assert_eq!((1..4).try_collect_array::<3>(), Ok([1, 2, 3]));
+    /// assert_eq!((1..4).try_collect::<[i32; 3]>(), Ok([1, 2, 3]));
+    /// ```
+    #[inline]
+    fn try_collect_array<const N: usize>(self) -> Result<[Self::Item; N], NonMatchingLenError>
+    where
+        Self: Sized,
+    {
+        self.try_collect()
+    }
+
+    /// Fills the remaining capacity of `target` with the elements of this iterator.
+    ///
+    /// See the [`TryExtendExact`] impl of the target for the state it is left in on error.
+    fn try_collect_into<B>(self, target: &mut B) -> Result<(), B::Error>
+    where
+        B: TryExtendExact<Self::Item>,
+        Self: Sized,
+    {
+        target.try_extend_exact(self)
+    }
+
+    /// Collects into an array like `try_collect()`, but returns the consumed elements on error
+    /// instead of dropping them.
+    #[cfg(feature = "alloc")]
+    fn try_collect_recoverable<const N: usize>(
+        self,
+    ) -> Result<[Self::Item; N], RecoverableLenError<Self::Item>>
+    where
+        Self: Sized,
+    {
+        let mut partial = PartialArray::<Self::Item, N>::new();
+        for val in self {
+            if partial.full() {
+                let mut elements = partial.into_vec();
+                elements.push(val);
+                return Err(RecoverableLenError {
+                    error: NonMatchingLenError::TooLong {
+                        expected: N,
+                        actual: N + 1,
+                        consumed: N + 1,
+                    },
+                    elements,
+                });
+            }
+            partial.push(val);
+        }
+        if !partial.full() {
+            return Err(RecoverableLenError {
+                error: NonMatchingLenError::TooShort {
+                    expected: N,
+                    actual: partial.len(),
+                    consumed: partial.len(),
+                },
+                elements: partial.into_vec(),
+            });
+        }
+        Ok(partial.into_array())
+    }
+
+    /// Collects into a `Vec`, failing if the iterator yields more than `MAX` elements.
+    ///
+    /// Unlike the array impls, any length up to `MAX` is accepted. This guards against untrusted
+    /// input of unreasonable size. The first element beyond the limit is consumed and dropped.
+    #[cfg(feature = "alloc")]
+    fn try_collect_bounded<const MAX: usize>(
+        self,
+    ) -> Result<alloc::vec::Vec<Self::Item>, LengthExceeded>
+    where
+        Self: Sized,
+    {
+        if self.size_hint().0 > MAX {
+            return Err(LengthExceeded { limit: MAX });
+        }
+        let mut vec = alloc::vec::Vec::new();
+        for val in self {
+            if vec.len() == MAX {
+                return Err(LengthExceeded { limit: MAX });
+            }
+            vec.push(val);
+        }
+        Ok(vec)
+    }
+
+    /// Collects into an array, filling the slots the iterator doesn't reach with
+    /// `Default::default()`.
+    ///
+    /// Fails only if the iterator yields more than `N` elements.
+    fn try_collect_padded<const N: usize>(mut self) -> Result<[Self::Item; N], NonMatchingLenError>
+    where
+        Self: Sized,
+        Self::Item: Default,
+    {
+        let mut partial = PartialArray::<Self::Item, N>::new();
+        partial.extend(&mut self);
+        if self.next().is_some() {
+            return Err(NonMatchingLenError::TooLong {
+                expected: N,
+                actual: N + 1,
+                consumed: N + 1,
+            });
+        }
+        while !partial.full() {
+            partial.push(Default::default());
+        }
+        Ok(partial.into_array())
+    }
+
+    /// Collects into an array of `Option`s, filling the slots the iterator doesn't reach with
+    /// `None`.
+    ///
+    /// Fails only if the iterator yields more than `N` elements, after consuming the first
+    /// surplus element.
+    #[inline]
+    fn collect_optional<const N: usize>(
+        self,
+    ) -> Result<[Option<Self::Item>; N], NonMatchingLenError>
+    where
+        Self: Sized,
+    {
+        self.map(Some).try_collect_padded()
+    }
+
+    /// Collects the first `N` elements into an array, ignoring any surplus.
+    ///
+    /// Fails only if the iterator yields fewer than `N` elements. Surplus elements are not
+    /// consumed, so calling this through a mutable reference leaves the iterator positioned right
+    /// after the collected prefix.
+    fn try_collect_prefix<const N: usize>(mut self) -> Result<[Self::Item; N], NonMatchingLenError>
+    where
+        Self: Sized,
+    {
+        let mut partial = PartialArray::<Self::Item, N>::new();
+        partial.extend(&mut self);
+        partial
+            .try_into_array()
+            .map_err(|partial| NonMatchingLenError::TooShort {
+                expected: N,
+                actual: partial.len(),
+                consumed: partial.len(),
+            })
+    }
+
+    /// Collects the first `N` elements into an array, and returns it together with the number of
+    /// surplus elements.
+    ///
+    /// Fails only if the iterator yields fewer than `N` elements, in which case the count is 0.
+    /// Surplus elements are drained and dropped.
+    fn try_collect_with_surplus<const N: usize>(
+        mut self,
+    ) -> (Result<[Self::Item; N], NonMatchingLenError>, usize)
+    where
+        Self: Sized,
+    {
+        match <&mut Self>::try_collect_prefix(&mut self) {
+            Ok(array) => (Ok(array), self.count()),
+            Err(err) => (Err(err), 0),
+        }
+    }
+
+    /// Collects the first `N` elements into an array, and returns it together with the rest of
+    /// the iterator.
+    ///
+    /// Fails if the iterator yields fewer than `N` elements.
+    fn try_collect_min<const N: usize>(
+        mut self,
+    ) -> Result<([Self::Item; N], Self), NonMatchingLenError>
+    where
+        Self: Sized,
+    {
+        let mut partial = PartialArray::<Self::Item, N>::new();
+        partial.extend(&mut self);
+        match partial.try_into_array() {
+            Ok(array) => Ok((array, self)),
+            Err(partial) => Err(NonMatchingLenError::TooShort {
+                expected: N,
+                actual: partial.len(),
+                consumed: partial.len(),
+            }),
+        }
+    }
+
+    /// Collects the elements before the first one for which `is_sentinel` returns `true` into an
+    /// array.
+    ///
+    /// The sentinel is consumed and dropped, and the iterator isn't advanced any further. Fails
+    /// with a `Length` error if there aren't exactly `N` elements before the sentinel, in which
+    /// case `consumed` of the error includes the sentinel. Fails with `MissingSentinel` if the
+    /// iterator ends before yielding a sentinel, even if it yielded exactly `N` elements.
+    fn try_collect_until<const N: usize, F>(
+        self,
+        mut is_sentinel: F,
+    ) -> Result<[Self::Item; N], SentinelError>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        let mut partial = PartialArray::<Self::Item, N>::new();
+        let mut consumed = 0;
+        for val in self {
+            consumed += 1;
+            if is_sentinel(&val) {
+                return partial.try_into_array().map_err(|partial| {
+                    SentinelError::Length(NonMatchingLenError::TooShort {
+                        expected: N,
+                        actual: partial.len(),
+                        consumed,
+                    })
+                });
+            }
+            if partial.full() {
+                return Err(SentinelError::Length(NonMatchingLenError::TooLong {
+                    expected: N,
+                    actual: N + 1,
+                    consumed,
+                }));
+            }
+            partial.push(val);
+        }
+        Err(SentinelError::MissingSentinel { consumed })
+    }
+
+    /// Maps the first `N` elements with `f` and collects them into an array, and returns it
+    /// together with the rest of the iterator.
+    ///
+    /// Fails if the iterator yields fewer than `N` elements.
+    fn try_collect_mapped_prefix<const N: usize, B, F>(
+        mut self,
+        mut f: F,
+    ) -> Result<([B; N], Self), NonMatchingLenError>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item) -> B,
+    {
+        let mut partial = PartialArray::<B, N>::new();
+        partial.extend(self.by_ref().map(&mut f));
+        match partial.try_into_array() {
+            Ok(array) => Ok((array, self)),
+            Err(partial) => Err(NonMatchingLenError::TooShort {
+                expected: N,
+                actual: partial.len(),
+                consumed: partial.len(),
+            }),
+        }
+    }
+
+    /// Collects the first `A` elements into one array and the next `B` elements into another.
+    ///
+    /// Like `try_collect()`, this fails if the iterator doesn't yield exactly `A + B` elements.
+    /// Use [`try_take_array()`](TryCollect::try_take_array) twice to ignore any surplus.
+    #[allow(clippy::type_complexity)]
+    fn try_split_collect<const A: usize, const B: usize>(
+        mut self,
+    ) -> Result<([Self::Item; A], [Self::Item; B]), NonMatchingLenError>
+    where
+        Self: Sized,
+    {
+        let expected = A + B;
+        check_size_hint(expected, self.size_hint())?;
+        let mut first = PartialArray::<Self::Item, A>::new();
+        let mut second = PartialArray::<Self::Item, B>::new();
+        first.extend(&mut self);
+        second.extend(&mut self);
+        if !second.full() {
+            let actual = first.len() + second.len();
+            return Err(NonMatchingLenError::TooShort {
+                expected,
+                actual,
+                consumed: actual,
+            });
+        }
+        if self.next().is_some() {
+            return Err(NonMatchingLenError::TooLong {
+                expected,
+                actual: expected + 1,
+                consumed: expected + 1,
+            });
+        }
+        Ok((first.into_array(), second.into_array()))
+    }
+
+    /// Pushes up to `N` elements into a [`PartialArray`], which is returned whether it is full or
+    /// not.
+    ///
+    /// No element is taken from the iterator once the array is full. The array can be filled
+    /// further later on, e.g. with [`PartialArray::extend()`].
+    ///
+    /// [`PartialArray`]: partial_array::PartialArray
+    /// [`PartialArray::extend()`]: partial_array::PartialArray::extend
+    #[inline]
+    fn collect_partial<const N: usize>(self) -> PartialArray<Self::Item, N>
+    where
+        Self: Sized,
+    {
+        let mut partial = PartialArray::new();
+        partial.extend(self);
+        partial
+    }
+
+    /// Takes the next `N` elements into an array, leaving the iterator positioned after them.
+    ///
+    /// Unlike `try_collect()`, this doesn't consume the iterator, so it can be called repeatedly
+    /// to read successive fixed-size frames. Fails if fewer than `N` elements are left, in which
+    /// case the iterator has been advanced by [`NonMatchingLenError::consumed()`] elements.
+    fn try_take_array<const N: usize>(&mut self) -> Result<[Self::Item; N], NonMatchingLenError>
+    where
+        Self: Sized,
+    {
+        let mut partial = PartialArray::<Self::Item, N>::new();
+        partial.extend(self);
+        partial
+            .try_into_array()
+            .map_err(|partial| NonMatchingLenError::TooShort {
+                expected: N,
+                actual: partial.len(),
+                consumed: partial.len(),
+            })
+    }
+
+    /// Collects the `Ok` values of an iterator of `Result`s into an array, stopping at the first
+    /// `Err`.
+    fn try_collect_ok<const N: usize, T, E>(self) -> Result<[T; N], CollectError<E>>
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+    {
+        let mut partial = PartialArray::<T, N>::new();
+        for item in self {
+            let val = item.map_err(CollectError::Item)?;
+            if partial.full() {
+                return Err(CollectError::Length(NonMatchingLenError::TooLong {
+                    expected: N,
+                    actual: N + 1,
+                    consumed: N + 1,
+                }));
+            }
+            partial.push(val);
+        }
+        partial.try_into_array().map_err(|partial| {
+            CollectError::Length(NonMatchingLenError::TooShort {
+                expected: N,
+                actual: partial.len(),
+                consumed: partial.len(),
+            })
+        })
+    }
+
+    /// Collects an iterator of pairs into two arrays, like `Iterator::unzip()`.
+    fn try_unzip_collect<const N: usize, A, B>(
+        self,
+    ) -> Result<([A; N], [B; N]), NonMatchingLenError>
+    where
+        Self: Sized + Iterator<Item = (A, B)>,
+    {
+        check_size_hint(N, self.size_hint())?;
+        let mut left = PartialArray::<A, N>::new();
+        let mut right = PartialArray::<B, N>::new();
+        for (a, b) in self {
+            if left.full() {
+                return Err(NonMatchingLenError::TooLong {
+                    expected: N,
+                    actual: N + 1,
+                    consumed: N + 1,
+                });
+            }
+            left.push(a);
+            right.push(b);
+        }
+        if !left.full() {
+            return Err(NonMatchingLenError::TooShort {
+                expected: N,
+                actual: left.len(),
+                consumed: left.len(),
+            });
+        }
+        Ok((left.into_array(), right.into_array()))
+    }
+
+    /// Splits an iterator of `Result`s into the `Ok` values and the `Err` values, without
+    /// stopping at the first `Err`.
+    ///
+    /// Fails with the item that didn't fit if either bucket overflows its capacity.
+    #[allow(clippy::type_complexity)]
+    fn try_partition_collect<const N: usize, const M: usize, T, E>(
+        self,
+    ) -> Result<(PartialArray<T, N>, PartialArray<E, M>), CapacityError<Result<T, E>>>
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+    {
+        let mut oks = PartialArray::new();
+        let mut errs = PartialArray::new();
+        for item in self {
+            match item {
+                Ok(val) if !oks.full() => oks.push(val),
+                Err(err) if !errs.full() => errs.push(err),
+                Ok(_) => return Err(CapacityError::new(item, N)),
+                Err(_) => return Err(CapacityError::new(item, M)),
+            }
+        }
+        Ok((oks, errs))
+    }
+
+    /// Splits an iterator of `Result`s into vectors of the `Ok` values and the `Err` values.
+    #[cfg(feature = "alloc")]
+    fn partition_results<T, E>(self) -> (alloc::vec::Vec<T>, alloc::vec::Vec<E>)
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+    {
+        let mut oks = alloc::vec::Vec::new();
+        let mut errs = alloc::vec::Vec::new();
+        for item in self {
+            match item {
+                Ok(val) => oks.push(val),
+                Err(err) => errs.push(err),
+            }
+        }
+        (oks, errs)
+    }
+
+    /// Collects into an array like `try_collect()`, but consumes the iterator from the back.
+    ///
+    /// The resulting array is in forward order, so on success it is the same as the result of
+    /// `try_collect()`. The difference is in which elements are consumed before an error is
+    /// detected, which matters when calling this through a mutable reference.
+    fn try_collect_back<const N: usize>(self) -> Result<[Self::Item; N], NonMatchingLenError>
+    where
+        Self: Sized + DoubleEndedIterator,
+    {
+        let mut array: [Self::Item; N] = self.rev().try_collect()?;
+        array.reverse();
+        Ok(array)
+    }
+
+    /// Collects pairs of the index and the element into an array, like
+    /// `self.enumerate().try_collect()`.
+    #[inline]
+    fn try_collect_enumerated<const N: usize>(
+        self,
+    ) -> Result<[(usize, Self::Item); N], NonMatchingLenError>
+    where
+        Self: Sized,
+    {
+        self.enumerate().try_collect()
+    }
+
+    /// Collects like `try_collect()`, but includes the type name of the iterator in the error.
+    ///
+    /// This helps to tell apart failures from different call sites. The type name is only meant
+    /// for diagnostics, see [`core::any::type_name()`]. This is available without the `alloc`
+    /// feature.
+    fn try_collect_diagnostic<C>(self) -> Result<C, DiagnosticError<C::Error>>
+    where
+        Self: Sized,
+        C: TryFromIterator<Self::Item>,
+    {
+        C::try_from_iter(self).map_err(|error| DiagnosticError {
+            error,
+            iter_type: core::any::type_name::<Self>(),
+        })
+    }
+
+    /// Collects into an array like `try_collect()`, but returns the first surplus element in the
+    /// error instead of dropping it.
+    ///
+    /// The size hint of the iterator is not used to detect a mismatch up front, so the surplus
+    /// element is always available if the iterator is too long.
+    fn try_collect_with_extra<const N: usize>(
+        mut self,
+    ) -> Result<[Self::Item; N], ExtraElementError<Self::Item>>
+    where
+        Self: Sized,
+    {
+        let mut partial = PartialArray::<Self::Item, N>::new();
+        partial.extend(&mut self);
+        let array = partial
+            .try_into_array()
+            .map_err(|partial| ExtraElementError::TooShort {
+                expected: N,
+                actual: partial.len(),
+            })?;
+        match self.next() {
+            Some(extra) => Err(ExtraElementError::TooLong { expected: N, extra }),
+            None => Ok(array),
+        }
+    }
+
+    /// Collects into an array in reverse order, so the first element ends up at the last index.
+    ///
+    /// Unlike [`try_collect_back()`](TryCollect::try_collect_back), this consumes the iterator
+    /// from the front, and only the placement of the elements is reversed.
+    #[inline]
+    fn try_collect_rev<const N: usize>(self) -> Result<[Self::Item; N], NonMatchingLenError>
+    where
+        Self: Sized,
+    {
+        let mut array: [Self::Item; N] = self.try_collect()?;
+        array.reverse();
+        Ok(array)
+    }
+
+    /// Collects into an array like `try_collect()`, checking the length of an
+    /// `ExactSizeIterator` once up front instead of checking each element.
+    ///
+    /// The reported length of the iterator is trusted beyond that check: if the iterator yields
+    /// more elements than it reports, the surplus is ignored. If it yields fewer, a `TooShort`
+    /// error is returned.
+    #[inline]
+    fn try_collect_exact_size<const N: usize>(self) -> Result<[Self::Item; N], NonMatchingLenError>
+    where
+        Self: Sized + ExactSizeIterator,
+    {
+        let len = self.len();
+        if len < N {
+            return Err(NonMatchingLenError::TooShort {
+                expected: N,
+                actual: len,
+                consumed: 0,
+            });
+        }
+        if len > N {
+            return Err(NonMatchingLenError::TooLong {
+                expected: N,
+                actual: len,
+                consumed: 0,
+            });
+        }
+        let mut partial = PartialArray::<Self::Item, N>::new();
+        partial.fill_from(self);
+        if !partial.full() {
+            return Err(NonMatchingLenError::TooShort {
+                expected: N,
+                actual: partial.len(),
+                consumed: partial.len(),
+            });
+        }
+        Ok(partial.into_array())
+    }
+
+    /// Collects a flat iterator into a two-dimensional array of `N` rows of `M` elements each, in
+    /// row-major order.
+    ///
+    /// Fails if the iterator doesn't yield exactly `M * N` elements. This is a method rather than
+    /// a `TryFromIterator` impl for `[[A; M]; N]`, since such an impl would overlap with the one
+    /// for `[A; N]` and break type inference for `try_collect::<[_; N]>()`.
+    fn try_collect_nested<const M: usize, const N: usize>(
+        self,
+    ) -> Result<[[Self::Item; M]; N], NonMatchingLenError>
+    where
+        Self: Sized,
+    {
+        let expected = M * N;
+        check_size_hint(expected, self.size_hint())?;
+        let mut rows = PartialArray::<[Self::Item; M], N>::new();
+        let mut row = PartialArray::<Self::Item, M>::new();
+        let mut actual = 0;
+        for val in self {
+            if actual == expected {
+                return Err(NonMatchingLenError::TooLong {
+                    expected,
+                    actual: expected + 1,
+                    consumed: expected + 1,
+                });
+            }
+            row.push(val);
+            actual += 1;
+            if row.full() {
+                rows.push(core::mem::take(&mut row).into_array());
+            }
+        }
+        if actual < expected {
+            return Err(NonMatchingLenError::TooShort {
+                expected,
+                actual,
+                consumed: actual,
+            });
+        }
+        // The rows are only incomplete at this point if they are empty, i.e. `M == 0`.
+        while !rows.full() {
+            rows.push(PartialArray::new().into_array());
+        }
+        Ok(rows.into_array())
+    }
+
+    /// Collects a flat iterator into a matrix with `ROWS` rows and `COLS` columns, filled in the
+    /// given `order`.
+    ///
+    /// Fails if the iterator doesn't yield exactly `ROWS * COLS` elements.
+    fn try_collect_matrix<const ROWS: usize, const COLS: usize>(
+        self,
+        order: Order,
+    ) -> Result<[[Self::Item; COLS]; ROWS], NonMatchingLenError>
+    where
+        Self: Sized,
+    {
+        match order {
+            Order::RowMajor => self.try_collect_nested(),
+            Order::ColumnMajor => {
+                let columns: [[Self::Item; ROWS]; COLS] = self.try_collect_nested()?;
+                let mut columns = columns.map(IntoIterator::into_iter);
+                let mut rows = PartialArray::<[Self::Item; COLS], ROWS>::new();
+                while !rows.full() {
+                    // Each column has exactly `ROWS` elements, so every row is complete.
+                    let mut row = PartialArray::<Self::Item, COLS>::new();
+                    row.extend(columns.iter_mut().filter_map(Iterator::next));
+                    rows.push(row.into_array());
+                }
+                Ok(rows.into_array())
+            }
+        }
+    }
+
+    /// Collects an iterator of arrays of `M` elements into one flat array of `TOTAL` elements.
+    ///
+    /// The elements are moved out of the inner arrays. Fails if the inner arrays don't add up to
+    /// exactly `TOTAL` elements, so `TOTAL` must be a multiple of `M` for this to succeed.
+    #[inline]
+    fn try_flatten_collect<const M: usize, const TOTAL: usize, T>(
+        self,
+    ) -> Result<[T; TOTAL], NonMatchingLenError>
+    where
+        Self: Sized + Iterator<Item = [T; M]>,
+    {
+        self.flatten().try_collect()
+    }
+
+    /// Collects into an array, checking each element with `validate` before it is added.
+    ///
+    /// Stops at the first element for which `validate` returns `false`, and fails with its index.
+    /// Otherwise, fails if the iterator doesn't yield exactly `N` elements.
+    fn try_collect_validated<const N: usize, F>(
+        self,
+        mut validate: F,
+    ) -> Result<[Self::Item; N], ValidateError>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        check_size_hint(N, self.size_hint())?;
+        let mut partial = PartialArray::<Self::Item, N>::new();
+        for val in self {
+            if partial.full() {
+                return Err(ValidateError::Length(NonMatchingLenError::TooLong {
+                    expected: N,
+                    actual: N + 1,
+                    consumed: N + 1,
+                }));
+            }
+            if !validate(&val) {
+                return Err(ValidateError::Invalid {
+                    index: partial.len(),
+                });
+            }
+            partial.push(val);
+        }
+        partial.try_into_array().map_err(|partial| {
+            ValidateError::Length(NonMatchingLenError::TooShort {
+                expected: N,
+                actual: partial.len(),
+                consumed: partial.len(),
+            })
+        })
+    }
+
+    /// Collects into an array, failing if two consecutive elements are equal.
+    ///
+    /// Only neighbours are compared, so this is cheap, but only detects all duplicates if the
+    /// iterator is sorted. Otherwise, fails if the iterator doesn't yield exactly `N` elements.
+    fn try_collect_dedup<const N: usize>(self) -> Result<[Self::Item; N], DedupError>
+    where
+        Self: Sized,
+        Self::Item: PartialEq,
+    {
+        check_size_hint(N, self.size_hint())?;
+        let mut partial = PartialArray::<Self::Item, N>::new();
+        for val in self {
+            if partial.full() {
+                return Err(DedupError::Length(NonMatchingLenError::TooLong {
+                    expected: N,
+                    actual: N + 1,
+                    consumed: N + 1,
+                }));
+            }
+            if partial.as_slice().last() == Some(&val) {
+                return Err(DedupError::Duplicate {
+                    index: partial.len(),
+                });
+            }
+            partial.push(val);
+        }
+        partial.try_into_array().map_err(|partial| {
+            DedupError::Length(NonMatchingLenError::TooShort {
+                expected: N,
+                actual: partial.len(),
+                consumed: partial.len(),
+            })
+        })
+    }
+
+    /// Applies a fallible transform to each element and collects the results into an array,
+    /// stopping at the first transform error.
+    fn try_map_collect<const N: usize, B, E, F>(self, f: F) -> Result<[B; N], CollectError<E>>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item) -> Result<B, E>,
+    {
+        self.map(f).try_collect_ok()
+    }
+
+    /// Returns an iterator over consecutive, non-overlapping arrays of `N` elements.
+    ///
+    /// If the length of the iterator isn't a multiple of `N`, the trailing elements are not
+    /// yielded, but can be retrieved with [`ChunksArray::into_remainder()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    ///
+    /// [`ChunksArray::into_remainder()`]: adapters::ChunksArray::into_remainder
+    fn chunks_array<const N: usize>(self) -> adapters::ChunksArray<Self, N>
+    where
+        Self: Sized,
+    {
+        adapters::ChunksArray::new(self)
+    }
+
+    /// Defers collecting into an array of `N` elements until
+    /// [`finish()`](adapters::LazyArrayCollect::finish) is called.
+    ///
+    /// This allows inspecting the size hint of the iterator and peeking at its first element
+    /// before committing to collect it.
+    fn try_collect_lazy<const N: usize>(self) -> adapters::LazyArrayCollect<Self, N>
+    where
+        Self: Sized,
+    {
+        adapters::LazyArrayCollect::new(self)
+    }
+
+    /// Returns an iterator over overlapping windows of `N` consecutive elements.
+    ///
+    /// The elements are cloned into each window they are part of. Nothing is yielded if the
+    /// iterator has fewer than `N` elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    fn windows_array<const N: usize>(self) -> adapters::WindowsArray<Self, N>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        adapters::WindowsArray::new(self)
+    }
 
-impl fmt::Display for NonMatchingLenError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "iterator length and array length do not match")
+    /// Collects an iterator of references into an array of clones of the referenced elements.
+    ///
+    /// This is the same as `self.cloned().try_collect()`. It is also the way to collect references
+    /// to `Copy` elements, since cloning them is a plain copy. There is no `TryFromIterator<&A>`
+    /// impl for `[A; N]`, neither for `A: Clone` nor for `A: Copy`, since it would make collecting
+    /// references into `[_; N]` ambiguous.
+    #[inline]
+    fn try_collect_cloned<'a, const N: usize, T>(self) -> Result<[T; N], NonMatchingLenError>
+    where
+        Self: Sized + Iterator<Item = &'a T>,
+        T: Clone + 'a,
+    {
+        self.cloned().try_collect()
     }
-}
 
-#[cfg(feature = "std")]
-impl std::error::Error for NonMatchingLenError {}
+    /// Copies the remaining elements of a contiguous iterator into an array.
+    ///
+    /// The length is checked once, and the elements are copied in bulk instead of one by one.
+    #[inline]
+    fn try_collect_copied<const N: usize>(self) -> Result<[Self::Element; N], NonMatchingLenError>
+    where
+        Self: Sized + ContiguousIterator,
+        Self::Element: Copy,
+    {
+        let slice = self.as_slice();
+        slice.try_into().map_err(|_| {
+            if slice.len() < N {
+                NonMatchingLenError::TooShort {
+                    expected: N,
+                    actual: slice.len(),
+                    consumed: 0,
+                }
+            } else {
+                NonMatchingLenError::TooLong {
+                    expected: N,
+                    actual: slice.len(),
+                    consumed: 0,
+                }
+            }
+        })
+    }
 
-impl<A, const N: usize> TryFromIterator<A> for [A; N] {
-    type Error = NonMatchingLenError;
-    fn try_from_iter<T>(iter: T) -> Result<Self, Self::Error>
+    /// Collects the values of an iterator of `Option`s into an array, failing at the first
+    /// `None`.
+    ///
+    /// A `None` before the array is full results in a `TooShort` error. Once the array is full,
+    /// any further item, including `None`, results in a `TooLong` error.
+    fn try_collect_some<const N: usize, T>(self) -> Result<[T; N], NonMatchingLenError>
     where
-        T: IntoIterator<Item = A>,
+        Self: Sized + Iterator<Item = Option<T>>,
     {
-        let mut partial = partial_array::PartialArray::<A, N>::new();
-        for val in iter {
+        let mut partial = PartialArray::<T, N>::new();
+        let mut consumed = 0;
+        for item in self {
+            consumed += 1;
             if partial.full() {
-                return Err(NonMatchingLenError);
+                return Err(NonMatchingLenError::TooLong {
+                    expected: N,
+                    actual: N + 1,
+                    consumed,
+                });
+            }
+            match item {
+                Some(val) => partial.push(val),
+                None => break,
             }
-            partial.push(val);
-        }
-        if !partial.full() {
-            return Err(NonMatchingLenError);
         }
-        Ok(partial.into_array())
+        partial
+            .try_into_array()
+            .map_err(|partial| NonMatchingLenError::TooShort {
+                expected: N,
+                actual: partial.len(),
+                consumed,
+            })
     }
-}
 
-pub trait TryCollect: Iterator {
-    fn try_collect<B>(self) -> Result<B, B::Error>
+    /// Collects the values of an iterator of `Option`s into an array, skipping any `None`.
+    ///
+    /// Fails if there are not exactly `N` `Some` values. Unlike
+    /// [`try_collect_some()`](TryCollect::try_collect_some), which treats a `None` as the end of
+    /// the input, this filters out the `None` items, and the counts in the error only include the
+    /// `Some` values.
+    #[inline]
+    fn try_collect_flatten<const N: usize, T>(self) -> Result<[T; N], NonMatchingLenError>
     where
-        B: TryFromIterator<Self::Item>,
-        Self: Sized,
+        Self: Sized + Iterator<Item = Option<T>>,
     {
-        TryFromIterator::try_from_iter(self)
+        self.flatten().try_collect()
     }
 }
 
 impl<I: Iterator> TryCollect for I {}
 
-mod partial_array {
-    use core::mem::MaybeUninit;
+#[cfg(feature = "alloc")]
+mod partial_box {
+    use alloc::boxed::Box;
+    use core::mem::{ManuallyDrop, MaybeUninit};
 
-    pub struct PartialArray<A, const N: usize> {
-        array: [MaybeUninit<A>; N],
+    pub struct PartialBox<A, const N: usize> {
+        buf: Box<MaybeUninit<[A; N]>>,
         len: usize,
     }
 
-    impl<A, const N: usize> PartialArray<A, N> {
+    impl<A, const N: usize> PartialBox<A, N> {
         pub fn new() -> Self {
             Self {
-                // assume_init() is safe here, since the value we are assuming to be initialized
-                // is an array of `MaybeUninit`s. This can be replaced with uninit_array() once
-                // it is stabilized.
-                array: unsafe { MaybeUninit::uninit().assume_init() },
+                buf: Box::new_uninit(),
                 len: 0,
             }
         }
 
         pub fn push(&mut self, val: A) {
-            assert!(self.len < N, "PartialArray already full.");
-            self.array[self.len].write(val);
+            assert!(self.len < N, "PartialBox already full.");
+            // The slot at index `len` is in bounds and not yet initialized.
+            unsafe { self.buf.as_mut_ptr().cast::<A>().add(self.len).write(val) };
             self.len += 1;
         }
 
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
         pub fn full(&self) -> bool {
             self.len == N
         }
 
-        pub fn into_array(self) -> [A; N] {
-            assert!(self.full(), "PartialArray not yet fully initialized.");
-            // Converting to an array is safe since we initialized all values.
-            // We can't transmute const generic arrays, so we have to convert pointers.
-            // We can't use array_assume_int() because it is unstable.
-            let array = unsafe { (&self.array as *const _ as *const [A; N]).read() };
-            core::mem::forget(self);
-            array
+        pub fn into_box(self) -> Box<[A; N]> {
+            assert!(self.full(), "PartialBox not yet fully initialized.");
+            let this = ManuallyDrop::new(self);
+            // Moving the box out is safe since `this` is never dropped, and assuming it to be
+            // initialized is safe since we initialized all values.
+            unsafe { core::ptr::read(&this.buf).assume_init() }
         }
     }
 
-    impl<A, const N: usize> Drop for PartialArray<A, N> {
+    impl<A, const N: usize> Drop for PartialBox<A, N> {
         fn drop(&mut self) {
-            for i in 0..self.len {
-                unsafe {
-                    // We can't use `assume_init()`, since we don't have ownership of the values.
-                    // We can't use `assume_init_drop()`, since it's unstable.
-                    core::ptr::drop_in_place(self.array[i].as_mut_ptr());
-                }
-            }
+            let init =
+                core::ptr::slice_from_raw_parts_mut(self.buf.as_mut_ptr().cast::<A>(), self.len);
+            // The first `len` values are initialized, and the box frees the memory afterwards.
+            unsafe { core::ptr::drop_in_place(init) };
         }
     }
 }
@@ -111,8 +1855,17 @@ mod partial_array {
 mod tests {
     extern crate std;
     use crate::partial_array::PartialArray;
-    use crate::{NonMatchingLenError, TryCollect};
-    use std::{cell::RefCell, vec, vec::Vec};
+    use crate::{
+        CollectError, DedupError, ExtraElementError, NonMatchingLenError, Order, SentinelError,
+        TryCollect, TryExtend, TryFromIterator, ValidateError,
+    };
+    use core::cell::RefCell;
+    use std::{
+        panic::AssertUnwindSafe,
+        rc::Rc,
+        string::{String, ToString},
+        vec,
+    };
 
     fn try_collect_common<const N: usize>() -> Result<[i32; N], NonMatchingLenError> {
         IntoIterator::into_iter([1, 2, 3]).try_collect()
@@ -120,60 +1873,1144 @@ mod tests {
 
     #[test]
     fn try_collect_array() {
-        assert_eq!(try_collect_common::<3>().unwrap(), [1, 2, 3]);
+        assert_eq!(try_collect_common::<3>(), Ok([1, 2, 3]));
     }
 
     #[test]
     fn try_collect_array_too_short() {
-        assert!(try_collect_common::<2>().is_err());
+        assert_eq!(
+            try_collect_common::<4>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 4,
+                actual: 3,
+                consumed: 0
+            })
+        );
     }
 
     #[test]
     fn try_collect_array_too_long() {
-        assert!(try_collect_common::<4>().is_err());
+        assert_eq!(
+            try_collect_common::<2>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 2,
+                actual: 3,
+                consumed: 0
+            })
+        );
     }
 
     #[test]
-    #[should_panic]
-    fn partial_array_not_full() {
-        let mut partial = PartialArray::<i32, 3>::new();
-        partial.push(1);
-        partial.push(2);
-        partial.into_array();
+    fn non_matching_len_error_display() {
+        let too_short = try_collect_common::<5>().unwrap_err();
+        assert_eq!(too_short.to_string(), "expected 5 elements, found 3");
+        let too_long = try_collect_common::<1>().unwrap_err();
+        assert_eq!(
+            too_long.to_string(),
+            "expected 1 elements, found at least 3"
+        );
+    }
+
+    struct Hinted<I> {
+        iter: I,
+        hint: (usize, Option<usize>),
+        consumed: usize,
+    }
+
+    impl<I: Iterator> Iterator for Hinted<I> {
+        type Item = I::Item;
+        fn next(&mut self) -> Option<Self::Item> {
+            self.consumed += 1;
+            self.iter.next()
+        }
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.hint
+        }
+    }
+
+    fn hinted(hint: (usize, Option<usize>)) -> Hinted<impl Iterator<Item = i32>> {
+        Hinted {
+            iter: IntoIterator::into_iter([1, 2, 3]),
+            hint,
+            consumed: 0,
+        }
+    }
+
+    #[test]
+    fn non_matching_len_error_consumed() {
+        let mut iter = (1..6).filter(|_| true);
+        let err = <[i32; 3]>::try_from_iter(&mut iter).unwrap_err();
+        assert_eq!(err.consumed(), 4);
+        assert_eq!(iter.next(), Some(5));
+        let err = <[i32; 3]>::try_from_iter(&mut iter).unwrap_err();
+        assert_eq!(err.consumed(), 0);
+        let err = (1..6).try_collect::<[i32; 3]>().unwrap_err();
+        assert_eq!(err.consumed(), 0);
+    }
+
+    #[test]
+    fn expected_len() {
+        fn expected_len<B: TryFromIterator<i32>>() -> Option<usize> {
+            B::EXPECTED_LEN
+        }
+        assert_eq!(expected_len::<[i32; 3]>(), Some(3));
+        assert_eq!(expected_len::<(i32, i32)>(), Some(2));
+        #[cfg(feature = "alloc")]
+        assert_eq!(
+            expected_len::<crate::bounded::BoundedVecDeque<i32, 3>>(),
+            None
+        );
+    }
+
+    /// An iterator that yields `Some` again after returning `None`.
+    struct Unfused(i32);
+
+    impl Iterator for Unfused {
+        type Item = i32;
+        fn next(&mut self) -> Option<i32> {
+            self.0 += 1;
+            (self.0 % 3 != 0).then_some(self.0)
+        }
+    }
+
+    #[test]
+    fn try_collect_unfused() {
+        assert_eq!(Unfused(0).try_collect(), Ok([1, 2]));
+        let mut iter = Unfused(0);
+        assert_eq!(
+            <[i32; 3]>::try_from_iter(&mut iter),
+            Err(NonMatchingLenError::TooShort {
+                expected: 3,
+                actual: 2,
+                consumed: 2,
+            })
+        );
+        assert_eq!(iter.next(), Some(4));
+        assert_eq!(Unfused(0).try_collect_prefix(), Ok([1, 2]));
+    }
+
+    #[test]
+    fn fixed_capacity() {
+        fn capacity<B: crate::FixedCapacity>() -> usize {
+            B::CAPACITY
+        }
+        assert_eq!(capacity::<[u8; 3]>(), 3);
+        assert_eq!(capacity::<PartialArray<u8, 4>>(), 4);
+        #[cfg(feature = "alloc")]
+        assert_eq!(capacity::<crate::bounded::BoundedString<5>>(), 5);
+    }
+
+    #[test]
+    fn try_collect_imprecise_size_hint() {
+        for hint in [(0, None), (0, Some(100)), (3, None), (2, Some(4))] {
+            assert_eq!(hinted(hint).try_collect(), Ok([1, 2, 3]));
+        }
+    }
+
+    #[test]
+    fn try_collect_size_hint_fails_fast() {
+        // The hints are lies, but they are trusted as far as they go.
+        let mut iter = hinted((1000, Some(1000)));
+        assert_eq!(
+            <[i32; 3]>::try_from_iter(&mut iter),
+            Err(NonMatchingLenError::TooLong {
+                expected: 3,
+                actual: 1000,
+                consumed: 0
+            })
+        );
+        assert_eq!(iter.consumed, 0);
+        let mut iter = hinted((0, Some(1)));
+        assert_eq!(
+            <[i32; 3]>::try_from_iter(&mut iter),
+            Err(NonMatchingLenError::TooShort {
+                expected: 3,
+                actual: 1,
+                consumed: 0
+            })
+        );
+        assert_eq!(iter.consumed, 0);
+    }
+
+    #[test]
+    fn try_from_array_const() {
+        const ARRAY: Result<[u8; 2], NonMatchingLenError> = crate::try_from_array_const([1, 2]);
+        assert_eq!(ARRAY, Ok([1, 2]));
+        assert_eq!(
+            crate::try_from_array_const::<_, 3, 2>([1, 2, 3]),
+            Err(NonMatchingLenError::TooLong {
+                expected: 2,
+                actual: 3,
+                consumed: 0,
+            })
+        );
+        assert_eq!(
+            crate::try_from_array_const::<u8, 0, 1>([]),
+            Err(NonMatchingLenError::TooShort {
+                expected: 1,
+                actual: 0,
+                consumed: 0,
+            })
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn non_matching_len_error_into_io_error() {
+        fn parse() -> std::io::Result<[i32; 2]> {
+            Ok((1..4).try_collect()?)
+        }
+        let err = parse().unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "expected 2 elements, found at least 3");
+        assert!(err.into_inner().unwrap().is::<NonMatchingLenError>());
     }
 
     #[test]
-    #[should_panic]
-    fn partial_array_too_full() {
-        let mut partial = PartialArray::<i32, 2>::new();
-        partial.push(1);
-        partial.push(2);
-        partial.push(3);
+    fn try_collect_taken() {
+        let mut values = [vec![1], vec![2, 3]];
+        assert_eq!(
+            crate::try_collect_taken(&mut values),
+            Ok([vec![1], vec![2, 3]])
+        );
+        assert!(values.iter().all(|v| v.is_empty()));
+        let mut values = [1, 2, 3];
+        assert_eq!(
+            crate::try_collect_taken::<2, _>(&mut values),
+            Err(NonMatchingLenError::TooLong {
+                expected: 2,
+                actual: 3,
+                consumed: 0,
+            })
+        );
+        assert_eq!(values, [1, 2, 3]);
     }
 
     #[test]
-    fn partial_array_drop() {
-        let drop_log = RefCell::new(vec![]);
-        struct Guard<'a> {
-            index: usize,
-            log: &'a RefCell<Vec<usize>>,
+    fn try_fill_slice() {
+        let mut buf = [0; 4];
+        assert_eq!(crate::try_fill_slice(&mut buf[1..], 1..4), Ok(()));
+        assert_eq!(buf, [0, 1, 2, 3]);
+        assert_eq!(
+            crate::try_fill_slice(&mut buf, (5..).take_while(|&x| x < 7)),
+            Err(NonMatchingLenError::TooShort {
+                expected: 4,
+                actual: 2,
+                consumed: 2,
+            })
+        );
+        assert_eq!(buf, [5, 6, 2, 3]);
+        assert_eq!(
+            crate::try_fill_slice(&mut buf[..1], (7..9).filter(|_| true)),
+            Err(NonMatchingLenError::TooLong {
+                expected: 1,
+                actual: 2,
+                consumed: 2,
+            })
+        );
+        assert_eq!(buf, [7, 6, 2, 3]);
+    }
+
+    #[test]
+    fn try_collect_array_of_refs() {
+        let strings = ["a".to_string(), "b".to_string()];
+        let refs = strings.iter().try_collect::<[_; 2]>().unwrap();
+        assert_eq!(refs, [&strings[0], &strings[1]]);
+    }
+
+    #[test]
+    fn try_collect_cloned() {
+        let strings = ["a".to_string(), "b".to_string()];
+        let array: [String; 2] = strings.iter().try_collect_cloned().unwrap();
+        assert_eq!(array, strings);
+        assert!(strings.iter().try_collect_cloned::<3, _>().is_err());
+        let numbers = [1, 2, 3];
+        assert_eq!(numbers[1..].iter().try_collect_cloned(), Ok([2, 3]));
+    }
+
+    #[test]
+    fn try_collect_empty_array() {
+        assert_eq!(core::iter::empty::<i32>().try_collect::<[i32; 0]>(), Ok([]));
+        assert_eq!(
+            (0..).try_collect::<[i32; 0]>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 0,
+                actual: usize::MAX,
+                consumed: 0
+            })
+        );
+        let mut iter = (0..3).filter(|_| true);
+        assert_eq!(
+            <[i32; 0]>::try_from_iter(&mut iter),
+            Err(NonMatchingLenError::TooLong {
+                expected: 0,
+                actual: 1,
+                consumed: 1
+            })
+        );
+        assert_eq!(iter.next(), Some(1));
+    }
+
+    #[test]
+    fn try_collect_zst_array() {
+        use core::marker::PhantomData;
+        assert_eq!(core::iter::repeat_n((), 3).try_collect(), Ok([(); 3]));
+        assert_eq!(
+            core::iter::repeat_n(PhantomData::<u32>, 2).try_collect(),
+            Ok([PhantomData; 2])
+        );
+        assert!(core::iter::repeat_n((), 4)
+            .try_collect::<[(); 3]>()
+            .is_err());
+    }
+
+    std::thread_local! {
+        static ZST_DROPS: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+    }
+
+    /// A zero-sized type counting its drops in a thread-local counter.
+    struct ZstDrop;
+
+    impl Drop for ZstDrop {
+        fn drop(&mut self) {
+            ZST_DROPS.with(|drops| drops.set(drops.get() + 1));
         }
+    }
+
+    fn zst_drops() -> usize {
+        ZST_DROPS.with(|drops| drops.replace(0))
+    }
+
+    #[test]
+    fn try_collect_zst_drop() {
+        let zsts = |n| (0..).take_while(move |&i| i < n).map(|_| ZstDrop);
+        zst_drops();
+        let array = zsts(3).try_collect::<[ZstDrop; 3]>().unwrap();
+        assert_eq!(zst_drops(), 0);
+        drop(array);
+        assert_eq!(zst_drops(), 3);
+        assert!(zsts(2).try_collect::<[ZstDrop; 3]>().is_err());
+        assert_eq!(zst_drops(), 2);
+        assert!(zsts(5).try_collect::<[ZstDrop; 3]>().is_err());
+        assert_eq!(zst_drops(), 4);
+        let mut partial = PartialArray::<ZstDrop, 4>::new();
+        partial.extend(zsts(3));
+        let mut iter = partial.into_iter();
+        drop(iter.next());
+        assert_eq!(zst_drops(), 1);
+        drop(iter);
+        assert_eq!(zst_drops(), 2);
+    }
+
+    #[test]
+    fn try_collect_panicking_iterator() {
+        struct Guard<'a>(usize, &'a RefCell<vec::Vec<usize>>);
         impl Drop for Guard<'_> {
             fn drop(&mut self) {
-                self.log.borrow_mut().push(self.index);
-            }
-        }
-        let guard = |i| Guard {
-            index: i,
-            log: &drop_log,
-        };
-        let mut partial = PartialArray::<Guard, 3>::new();
-        partial.push(guard(0));
-        partial.push(guard(1));
-        partial.push(guard(2));
-        let array = partial.into_array();
-        assert!(drop_log.borrow().is_empty());
-        drop(array);
-        assert_eq!(&*drop_log.borrow(), &[0, 1, 2]);
+                self.1.borrow_mut().push(self.0);
+            }
+        }
+        let log = RefCell::new(vec![]);
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            (0..5)
+                .map(|i| {
+                    assert!(i != 2, "iterator panicked");
+                    Guard(i, &log)
+                })
+                .try_collect::<[Guard; 5]>()
+        }));
+        assert!(result.is_err());
+        assert_eq!(*log.borrow(), [0, 1]);
+    }
+
+    #[test]
+    fn try_collect_nested() {
+        assert_eq!((0..6).try_collect_nested(), Ok([[0, 1, 2], [3, 4, 5]]));
+        assert_eq!((0..0).try_collect_nested(), Ok([[0; 0]; 2]));
+        assert_eq!(
+            (0..6).filter(|_| true).try_collect_nested::<2, 2>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 4,
+                actual: 5,
+                consumed: 5
+            })
+        );
+        assert_eq!(
+            (0..6).filter(|_| true).try_collect_nested::<4, 2>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 8,
+                actual: 6,
+                consumed: 0
+            })
+        );
+    }
+
+    #[test]
+    fn try_collect_nested_drop() {
+        let val = Rc::new(());
+        // Runs out in the middle of the second row.
+        let iter = core::iter::repeat_with(|| val.clone()).take(5);
+        assert!(iter.try_collect_nested::<3, 3>().is_err());
+        assert_eq!(Rc::strong_count(&val), 1);
+    }
+
+    #[test]
+    fn try_collect_array_infers_element_type() {
+        let result = (0..3).try_collect::<[_; 3]>();
+        assert_eq!(std::format!("{:?}", result), "Ok([0, 1, 2])");
+        let v = [1, 2, 3];
+        let a: [_; 3] = v.iter().copied().try_collect().unwrap();
+        assert_eq!(a, [1, 2, 3]);
+    }
+
+    #[test]
+    fn try_collect_unit() {
+        assert_eq!(core::iter::empty::<i32>().try_collect(), Ok(()));
+        assert_eq!(
+            (1..4).filter(|_| true).try_collect::<()>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 0,
+                actual: 1,
+                consumed: 1
+            })
+        );
+    }
+
+    #[test]
+    fn try_collect_tuple() {
+        let iter = || IntoIterator::into_iter([1, 2, 3]);
+        assert_eq!(iter().try_collect(), Ok((1, 2, 3)));
+        assert_eq!(
+            iter().try_collect::<(i32, i32)>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 2,
+                actual: 3,
+                consumed: 0
+            })
+        );
+        assert_eq!(
+            iter().try_collect::<(i32, i32, i32, i32)>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 4,
+                actual: 3,
+                consumed: 0
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn try_collect_box() {
+        use alloc::boxed::Box;
+        let iter = || IntoIterator::into_iter([1, 2, 3]);
+        assert_eq!(iter().try_collect(), Ok(Box::new([1, 2, 3])));
+        assert_eq!(
+            iter().try_collect::<Box<[i32; 2]>>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 2,
+                actual: 3,
+                consumed: 0
+            })
+        );
+        assert_eq!(
+            iter().try_collect::<Box<[i32; 4]>>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 4,
+                actual: 3,
+                consumed: 0
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    // Far too slow to interpret.
+    #[cfg_attr(miri, ignore)]
+    fn try_collect_box_large() {
+        use alloc::boxed::Box;
+        // Much larger than the stack of the test threads.
+        const LEN: usize = 1 << 24;
+        let boxed: Box<[u8; LEN]> = core::iter::repeat_n(7, LEN).try_collect().unwrap();
+        assert!(boxed.iter().all(|&x| x == 7));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn boxed_slice_from_iter() {
+        let rc = Rc::new(());
+        let rcs = |n| (0..).take_while(move |&i| i < n).map(|_| rc.clone());
+        assert_eq!(crate::boxed_slice_from_iter(3, rcs(3)).unwrap().len(), 3);
+        assert_eq!(
+            crate::boxed_slice_from_iter(3, rcs(2)).unwrap_err(),
+            NonMatchingLenError::TooShort {
+                expected: 3,
+                actual: 2,
+                consumed: 2,
+            }
+        );
+        assert_eq!(
+            crate::boxed_slice_from_iter(3, rcs(5)).unwrap_err(),
+            NonMatchingLenError::TooLong {
+                expected: 3,
+                actual: 4,
+                consumed: 4,
+            }
+        );
+        assert_eq!(Rc::strong_count(&rc), 1);
+        assert!(crate::boxed_slice_from_iter(0, core::iter::empty::<()>()).is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn try_collect_box_drop() {
+        use alloc::boxed::Box;
+        let iter = IntoIterator::into_iter([Box::new(1), Box::new(2)]);
+        assert!(iter.try_collect::<Box<[Box<i32>; 3]>>().is_err());
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "error_in_core"))]
+    fn non_matching_len_error_is_error() {
+        let err: &dyn core::error::Error = &try_collect_common::<4>().unwrap_err();
+        assert_eq!(err.to_string(), "expected 4 elements, found 3");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn try_collect_recoverable_too_short() {
+        let err = IntoIterator::into_iter([1, 2, 3])
+            .try_collect_recoverable::<4>()
+            .unwrap_err();
+        assert!(matches!(err.error(), NonMatchingLenError::TooShort { .. }));
+        assert_eq!(err.into_elements(), [1, 2, 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn try_collect_recoverable_too_long() {
+        let err = IntoIterator::into_iter([1, 2, 3])
+            .try_collect_recoverable::<1>()
+            .unwrap_err();
+        assert!(matches!(err.error(), NonMatchingLenError::TooLong { .. }));
+        assert_eq!(err.elements(), [1, 2]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn try_collect_bounded() {
+        assert_eq!((1..3).try_collect_bounded::<3>(), Ok(vec![1, 2]));
+        assert_eq!((1..4).try_collect_bounded::<3>(), Ok(vec![1, 2, 3]));
+        let err = (1..).try_collect_bounded::<3>().unwrap_err();
+        assert_eq!(err.limit(), 3);
+        let mut iter = (1..6).filter(|_| true);
+        assert!((&mut iter).try_collect_bounded::<3>().is_err());
+        assert_eq!(iter.next(), Some(5));
+    }
+
+    #[test]
+    fn try_collect_padded() {
+        assert_eq!((1..3).try_collect_padded(), Ok([1, 2, 0, 0]));
+        assert_eq!((1..3).try_collect_padded(), Ok([1, 2]));
+        assert_eq!(
+            (1..3).try_collect_padded::<1>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 1,
+                actual: 2,
+                consumed: 2
+            })
+        );
+    }
+
+    #[test]
+    fn collect_optional() {
+        assert_eq!((1..3).collect_optional(), Ok([Some(1), Some(2), None]));
+        assert_eq!((1..3).collect_optional(), Ok([Some(1), Some(2)]));
+        assert!((1..3).collect_optional::<1>().is_err());
+    }
+
+    #[test]
+    fn try_collect_prefix() {
+        let mut iter = 1..6;
+        assert_eq!((&mut iter).try_collect_prefix(), Ok([1, 2, 3]));
+        assert_eq!(iter.next(), Some(4));
+        assert_eq!(
+            iter.try_collect_prefix::<2>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 2,
+                actual: 1,
+                consumed: 1
+            })
+        );
+    }
+
+    #[test]
+    fn try_collect_with_surplus() {
+        assert_eq!((1..4).try_collect_with_surplus(), (Ok([1, 2, 3]), 0));
+        assert_eq!((1..9).try_collect_with_surplus(), (Ok([1, 2, 3]), 5));
+        assert_eq!(
+            (1..3).try_collect_with_surplus::<3>(),
+            (
+                Err(NonMatchingLenError::TooShort {
+                    expected: 3,
+                    actual: 2,
+                    consumed: 2
+                }),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn try_collect_min() {
+        let (header, body) = (1..6).try_collect_min::<2>().unwrap();
+        assert_eq!(header, [1, 2]);
+        assert!(body.eq(3..6));
+        assert_eq!(
+            (1..3).try_collect_min::<3>().map(|(header, _)| header),
+            Err(NonMatchingLenError::TooShort {
+                expected: 3,
+                actual: 2,
+                consumed: 2
+            })
+        );
+    }
+
+    #[test]
+    fn try_collect_until() {
+        let bytes = [1u8, 2, 3, 0, 4, 0];
+        let mut iter = bytes.iter().copied();
+        assert_eq!((&mut iter).try_collect_until(|&b| b == 0), Ok([1, 2, 3]));
+        assert_eq!(iter.next(), Some(4));
+        assert_eq!(
+            bytes.iter().try_collect_until::<4, _>(|&&b| b == 0),
+            Err(SentinelError::Length(NonMatchingLenError::TooShort {
+                expected: 4,
+                actual: 3,
+                consumed: 4,
+            }))
+        );
+        assert_eq!(
+            bytes.iter().try_collect_until::<2, _>(|&&b| b == 0),
+            Err(SentinelError::Length(NonMatchingLenError::TooLong {
+                expected: 2,
+                actual: 3,
+                consumed: 3,
+            }))
+        );
+    }
+
+    #[test]
+    fn try_collect_until_missing_sentinel() {
+        let err = (1..3).try_collect_until::<2, _>(|&x| x == 0).unwrap_err();
+        assert_eq!(err, SentinelError::MissingSentinel { consumed: 2 });
+        assert_eq!(err.to_string(), "no sentinel after 2 elements");
+        assert_eq!(
+            (1..2).try_collect_until::<2, _>(|&x| x == 0),
+            Err(SentinelError::MissingSentinel { consumed: 1 })
+        );
+    }
+
+    #[test]
+    fn try_collect_mapped_prefix() {
+        let (header, body) = "3 4 a b c"
+            .split(' ')
+            .try_collect_mapped_prefix::<2, _, _>(|s| s.parse::<u8>())
+            .unwrap();
+        assert_eq!(header, [Ok(3), Ok(4)]);
+        assert!(body.eq(["a", "b", "c"]));
+        let mut iter = 1..6;
+        let (header, _) = (&mut iter).try_collect_mapped_prefix(|x| x * 10).unwrap();
+        assert_eq!(header, [10, 20, 30]);
+        assert_eq!(iter.next(), Some(4));
+        assert_eq!(
+            (1..3)
+                .try_collect_mapped_prefix::<3, _, _>(|x| x * 10)
+                .map(|(header, _)| header),
+            Err(NonMatchingLenError::TooShort {
+                expected: 3,
+                actual: 2,
+                consumed: 2
+            })
+        );
+    }
+
+    #[test]
+    fn collect_partial() {
+        let mut partial = (1..3).collect_partial::<4>();
+        assert_eq!(partial.as_slice(), [1, 2]);
+        partial.extend(3..);
+        assert_eq!(partial.into_array(), [1, 2, 3, 4]);
+        let mut iter = 1..6;
+        assert!((&mut iter).collect_partial::<3>().full());
+        assert_eq!(iter.next(), Some(4));
+    }
+
+    #[test]
+    fn try_split_collect() {
+        let bytes = |n| (0..).take_while(move |&i| i < n);
+        assert_eq!(bytes(5).try_split_collect(), Ok(([0, 1], [2, 3, 4])));
+        assert_eq!(
+            bytes(3).try_split_collect::<2, 3>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 5,
+                actual: 3,
+                consumed: 3,
+            })
+        );
+        assert_eq!(
+            bytes(6).try_split_collect::<2, 3>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 5,
+                actual: 6,
+                consumed: 6,
+            })
+        );
+    }
+
+    #[test]
+    fn try_take_array() {
+        let mut iter = 1..8;
+        assert_eq!(iter.try_take_array(), Ok([1, 2, 3]));
+        assert_eq!(iter.try_take_array(), Ok([4, 5, 6]));
+        assert_eq!(
+            iter.try_take_array::<3>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 3,
+                actual: 1,
+                consumed: 1
+            })
+        );
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn try_collect_ok() {
+        let ok = |n| (0..n).map(Ok::<_, &str>);
+        assert_eq!(ok(3).try_collect_ok(), Ok([0, 1, 2]));
+        assert_eq!(
+            ok(3).try_collect_ok::<4, _, _>(),
+            Err(CollectError::Length(NonMatchingLenError::TooShort {
+                expected: 4,
+                actual: 3,
+                consumed: 3
+            }))
+        );
+        assert_eq!(
+            ok(3).try_collect_ok::<2, _, _>(),
+            Err(CollectError::Length(NonMatchingLenError::TooLong {
+                expected: 2,
+                actual: 3,
+                consumed: 3
+            }))
+        );
+        let mut iter = ok(2).chain([Err("bad"), Ok(3)]);
+        assert_eq!(
+            (&mut iter).try_collect_ok::<3, _, _>(),
+            Err(CollectError::Item("bad"))
+        );
+        assert_eq!(iter.next(), Some(Ok(3)));
+    }
+
+    #[test]
+    fn try_unzip_collect() {
+        let pairs = |n| (0..).take_while(move |&i| i < n).map(|i| (i, Rc::new(i)));
+        let (left, right) = pairs(3).try_unzip_collect().unwrap();
+        assert_eq!(left, [0, 1, 2]);
+        assert_eq!(right.map(|rc| *rc), [0, 1, 2]);
+        assert_eq!(
+            pairs(2).try_unzip_collect::<3, _, _>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 3,
+                actual: 2,
+                consumed: 2,
+            })
+        );
+        assert_eq!(
+            pairs(4).try_unzip_collect::<3, _, _>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 3,
+                actual: 4,
+                consumed: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn try_partition_collect() {
+        let items = || IntoIterator::into_iter([Ok(1), Err("a"), Ok(2), Err("b"), Ok(3)]);
+        let (oks, errs) = items().try_partition_collect::<3, 2, _, _>().unwrap();
+        assert_eq!(oks.as_slice(), [1, 2, 3]);
+        assert_eq!(errs.as_slice(), ["a", "b"]);
+        let err = items().try_partition_collect::<4, 1, _, _>().unwrap_err();
+        assert_eq!(err.into_element(), Err("b"));
+        let err = items().try_partition_collect::<2, 4, _, _>().unwrap_err();
+        assert_eq!(err.capacity(), 2);
+        assert_eq!(err.into_element(), Ok(3));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn partition_results() {
+        let items = IntoIterator::into_iter([Ok(1), Err("a"), Ok(2)]);
+        assert_eq!(items.partition_results(), (vec![1, 2], vec!["a"]));
+    }
+
+    #[test]
+    fn try_collect_back() {
+        assert_eq!((1..4).try_collect_back(), Ok([1, 2, 3]));
+        let mut iter = (1..6).filter(|_| true);
+        assert_eq!(
+            (&mut iter).try_collect_back::<3>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 3,
+                actual: 4,
+                consumed: 4
+            })
+        );
+        assert_eq!(iter.next_back(), Some(1));
+    }
+
+    #[test]
+    fn try_collect_enumerated() {
+        assert_eq!(
+            "ab".chars().try_collect_enumerated(),
+            Ok([(0, 'a'), (1, 'b')])
+        );
+        assert!("abc".chars().try_collect_enumerated::<2>().is_err());
+    }
+
+    #[test]
+    fn try_collect_diagnostic() {
+        assert_eq!((1..3).try_collect_diagnostic(), Ok([1, 2]));
+        let err = (1..4).try_collect_diagnostic::<[i32; 2]>().unwrap_err();
+        assert_eq!(
+            *err.error(),
+            NonMatchingLenError::TooLong {
+                expected: 2,
+                actual: 3,
+                consumed: 0,
+            }
+        );
+        assert_eq!(
+            err.iter_type(),
+            core::any::type_name::<core::ops::Range<i32>>()
+        );
+        assert_eq!(
+            err.to_string(),
+            std::format!(
+                "expected 2 elements, found at least 3 (collecting from `{}`)",
+                err.iter_type()
+            )
+        );
+    }
+
+    #[test]
+    fn try_collect_with_extra() {
+        assert_eq!((1..3).try_collect_with_extra(), Ok([1, 2]));
+        let err = (1..5).try_collect_with_extra::<2>().unwrap_err();
+        assert_eq!(
+            err,
+            ExtraElementError::TooLong {
+                expected: 2,
+                extra: 3
+            }
+        );
+        assert_eq!(err.to_string(), "expected 2 elements, found more");
+        assert_eq!(err.into_extra(), Some(3));
+        let err = (1..3).try_collect_with_extra::<3>().unwrap_err();
+        assert_eq!(err.into_extra(), None);
+        assert_eq!(
+            err.into_len_error(),
+            NonMatchingLenError::TooShort {
+                expected: 3,
+                actual: 2,
+                consumed: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn try_collect_rev() {
+        assert_eq!((1..4).try_collect_rev(), Ok([3, 2, 1]));
+        let mut iter = (1..6).filter(|_| true);
+        assert!((&mut iter).try_collect_rev::<3>().is_err());
+        assert_eq!(iter.next(), Some(5));
+    }
+
+    #[test]
+    fn try_collect_exact_size() {
+        assert_eq!(
+            vec![1, 2, 3].into_iter().try_collect_exact_size(),
+            Ok([1, 2, 3])
+        );
+        assert_eq!(
+            (1..4).try_collect_exact_size::<2>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 2,
+                actual: 3,
+                consumed: 0
+            })
+        );
+        assert_eq!(
+            (1..4).try_collect_exact_size::<4>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 4,
+                actual: 3,
+                consumed: 0
+            })
+        );
+        // An iterator reporting the wrong length.
+        struct Misreported(core::ops::Range<i32>, usize);
+        impl Iterator for Misreported {
+            type Item = i32;
+            fn next(&mut self) -> Option<i32> {
+                self.0.next()
+            }
+        }
+        impl ExactSizeIterator for Misreported {
+            fn len(&self) -> usize {
+                self.1
+            }
+        }
+        assert_eq!(
+            Misreported(1..3, 3).try_collect_exact_size::<3>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 3,
+                actual: 2,
+                consumed: 2
+            })
+        );
+        assert_eq!(Misreported(1..5, 2).try_collect_exact_size(), Ok([1, 2]));
+    }
+
+    #[test]
+    fn try_collect_matrix() {
+        assert_eq!(
+            (0..6).try_collect_matrix(Order::RowMajor),
+            Ok([[0, 1, 2], [3, 4, 5]])
+        );
+        assert_eq!(
+            (0..6).try_collect_matrix(Order::ColumnMajor),
+            Ok([[0, 2, 4], [1, 3, 5]])
+        );
+        let matrix: [[usize; 4]; 3] = (0..12).try_collect_matrix(Order::ColumnMajor).unwrap();
+        for k in 0..12 {
+            assert_eq!(matrix[k % 3][k / 3], k);
+        }
+        assert_eq!(
+            (0..5).try_collect_matrix::<2, 3>(Order::ColumnMajor),
+            Err(NonMatchingLenError::TooShort {
+                expected: 6,
+                actual: 5,
+                consumed: 0,
+            })
+        );
+        assert_eq!(
+            core::iter::empty::<i32>().try_collect_matrix(Order::ColumnMajor),
+            Ok([[], []])
+        );
+        let rc = Rc::new(());
+        let rcs = core::iter::repeat_with(|| rc.clone()).take(6);
+        let matrix = rcs.try_collect_matrix::<3, 2>(Order::ColumnMajor).unwrap();
+        assert_eq!(Rc::strong_count(&rc), 7);
+        drop(matrix);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn try_flatten_collect() {
+        let rows = |n| (0..).take_while(move |&i| i < n).map(|i| [i, i + 10]);
+        assert_eq!(rows(2).try_flatten_collect(), Ok([0, 10, 1, 11]));
+        assert_eq!(
+            rows(2).try_flatten_collect::<2, 5, _>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 5,
+                actual: 4,
+                consumed: 4,
+            })
+        );
+        let rc = Rc::new(());
+        let rcs = (0..3).map(|_| [rc.clone(), rc.clone()]);
+        assert!(rcs.try_flatten_collect::<2, 3, _>().is_err());
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn try_collect_validated() {
+        let mut last = 0;
+        let mut ascending = move |&x: &i32| core::mem::replace(&mut last, x) < x;
+        assert_eq!(
+            IntoIterator::into_iter([1, 3, 4]).try_collect_validated(&mut ascending),
+            Ok([1, 3, 4])
+        );
+        let rc = Rc::new(0);
+        let mut iter = IntoIterator::into_iter([1, 2, 0, 3]).map(|x| (x, rc.clone()));
+        assert_eq!(
+            (&mut iter).try_collect_validated::<4, _>(|&(x, _)| x > 0),
+            Err(ValidateError::Invalid { index: 2 })
+        );
+        assert_eq!(Rc::strong_count(&rc), 1);
+        assert_eq!(iter.next().map(|(x, _)| x), Some(3));
+        assert!(matches!(
+            (1..3).try_collect_validated::<3, _>(|_| true),
+            Err(ValidateError::Length(NonMatchingLenError::TooShort { .. }))
+        ));
+    }
+
+    #[test]
+    fn try_collect_dedup() {
+        let iter = || IntoIterator::into_iter([1, 2, 2, 3]);
+        assert_eq!(iter().take(2).try_collect_dedup(), Ok([1, 2]));
+        assert_eq!(
+            iter().try_collect_dedup::<4>(),
+            Err(DedupError::Duplicate { index: 2 })
+        );
+        assert_eq!(
+            IntoIterator::into_iter([1, 2, 1]).try_collect_dedup(),
+            Ok([1, 2, 1])
+        );
+        assert!(matches!(
+            iter().take(2).try_collect_dedup::<3>(),
+            Err(DedupError::Length(NonMatchingLenError::TooShort { .. }))
+        ));
+    }
+
+    #[test]
+    fn try_map_collect() {
+        let parse = |s: &str| s.parse::<i32>();
+        assert_eq!(["1", "2"].into_iter().try_map_collect(parse), Ok([1, 2]));
+        assert!(matches!(
+            ["1", "x"].into_iter().try_map_collect::<2, _, _, _>(parse),
+            Err(CollectError::Item(_))
+        ));
+        assert!(matches!(
+            ["1", "2"].into_iter().try_map_collect::<3, _, _, _>(parse),
+            Err(CollectError::Length(NonMatchingLenError::TooShort { .. }))
+        ));
+    }
+
+    #[test]
+    fn try_collect_copied() {
+        let slice = [1, 2, 3, 4];
+        let mut iter = slice.iter();
+        iter.next();
+        assert_eq!(iter.try_collect_copied(), Ok([2, 3, 4]));
+        #[cfg(feature = "alloc")]
+        assert_eq!(vec![1, 2].into_iter().try_collect_copied(), Ok([1, 2]));
+        assert_eq!(
+            slice.iter().try_collect_copied::<5>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 5,
+                actual: 4,
+                consumed: 0
+            })
+        );
+        assert_eq!(
+            IntoIterator::into_iter(slice).try_collect_copied::<3>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 3,
+                actual: 4,
+                consumed: 0
+            })
+        );
+    }
+
+    #[test]
+    fn try_collect_some() {
+        let iter = || IntoIterator::into_iter([Some(1), Some(2), None, Some(4)]);
+        assert_eq!(iter().take(2).try_collect_some(), Ok([1, 2]));
+        assert_eq!(
+            iter().try_collect_some::<3, _>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 3,
+                actual: 2,
+                consumed: 3
+            })
+        );
+        assert_eq!(
+            iter().take(1).try_collect_some::<3, _>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 3,
+                actual: 1,
+                consumed: 1
+            })
+        );
+        assert_eq!(
+            iter().try_collect_some::<2, _>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 2,
+                actual: 3,
+                consumed: 3
+            })
+        );
+    }
+
+    #[test]
+    fn try_extend_partial_array() {
+        let mut partial = PartialArray::<i32, 4>::new();
+        assert_eq!(partial.try_extend(1..3), Ok(()));
+        assert_eq!(partial.try_extend(3..5), Ok(()));
+        assert_eq!(partial.as_slice(), [1, 2, 3, 4]);
+        assert_eq!(partial.try_extend(5..7).unwrap_err().into_element(), 5);
+        let mut partial = PartialArray::<i32, 3>::new();
+        assert!(partial.try_extend(1..5).is_err());
+        assert_eq!(partial.as_slice(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn try_collect_flatten() {
+        let iter = || IntoIterator::into_iter([Some(1), None, Some(2), None, Some(3)]);
+        assert_eq!(iter().try_collect_flatten(), Ok([1, 2, 3]));
+        assert_eq!(
+            iter().try_collect_flatten::<4, _>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 4,
+                actual: 3,
+                consumed: 3,
+            })
+        );
+        assert_eq!(
+            iter().try_collect_flatten::<2, _>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 2,
+                actual: 3,
+                consumed: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn try_collect_into_partial_array() {
+        let mut partial = PartialArray::<i32, 4>::new();
+        partial.push(0);
+        assert_eq!(
+            IntoIterator::into_iter([1, 2, 3]).try_collect_into(&mut partial),
+            Ok(())
+        );
+        assert_eq!(partial.into_array(), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn try_collect_into_partial_array_mismatch() {
+        let mut partial = PartialArray::<i32, 4>::new();
+        partial.push(0);
+        assert_eq!(
+            (1..).take_while(|&x| x < 3).try_collect_into(&mut partial),
+            Err(NonMatchingLenError::TooShort {
+                expected: 3,
+                actual: 2,
+                consumed: 2
+            })
+        );
+        assert_eq!(partial.len(), 3);
+        assert_eq!(
+            (3..5).filter(|_| true).try_collect_into(&mut partial),
+            Err(NonMatchingLenError::TooLong {
+                expected: 1,
+                actual: 2,
+                consumed: 2
+            })
+        );
+        assert_eq!(partial.into_array(), [0, 1, 2, 3]);
     }
 }
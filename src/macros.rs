@@ -0,0 +1,129 @@
+use core::fmt;
+
+/// Builds an array from `Result` or `Option` expressions, short-circuiting on the first failure.
+///
+/// The expressions are evaluated in order. If all of them succeed, the macro evaluates to
+/// `Ok([...])` with the unwrapped values. Otherwise it evaluates to the first error, and the
+/// remaining expressions are not evaluated. A `None` results in a [`NoneError`]. Like the `?`
+/// operator, the error is converted with [`From::from()`], so the expressions may have different
+/// error types as long as they all convert into the error type of the result.
+///
+/// ```
+/// use std::num::ParseIntError;
+/// use try_collect::{try_array, NoneError};
+///
+/// let array: Result<[i32; 3], ParseIntError> = try_array!["1".parse(), "2".parse(), "3".parse()];
+/// assert_eq!(array, Ok([1, 2, 3]));
+/// let array: Result<[i32; 3], NoneError> = try_array![Some(1), None, Some(3)];
+/// assert_eq!(array, Err(NoneError));
+/// ```
+#[macro_export]
+macro_rules! try_array {
+    ($($item:expr),* $(,)?) => {
+        'try_array: {
+            ::core::result::Result::Ok([$(
+                match $crate::__private::IntoResult::into_result($item) {
+                    ::core::result::Result::Ok(val) => val,
+                    ::core::result::Result::Err(err) => {
+                        break 'try_array ::core::result::Result::Err(::core::convert::From::from(err))
+                    }
+                }
+            ),*])
+        }
+    };
+}
+
+/// Error returned by [`try_array!`] when one of the expressions is `None`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NoneError;
+
+impl fmt::Display for NoneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected a value, found None")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NoneError {}
+
+#[cfg(all(feature = "error_in_core", not(feature = "std")))]
+impl core::error::Error for NoneError {}
+
+pub trait IntoResult {
+    type Ok;
+    type Err;
+    fn into_result(self) -> Result<Self::Ok, Self::Err>;
+}
+
+impl<T, E> IntoResult for Result<T, E> {
+    type Ok = T;
+    type Err = E;
+    fn into_result(self) -> Self {
+        self
+    }
+}
+
+impl<T> IntoResult for Option<T> {
+    type Ok = T;
+    type Err = NoneError;
+    fn into_result(self) -> Result<T, NoneError> {
+        self.ok_or(NoneError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::NoneError;
+    use core::cell::Cell;
+    use core::num::ParseIntError;
+
+    #[test]
+    fn try_array_result() {
+        let array: Result<[i32; 2], &str> = try_array![Ok(1), Ok(2)];
+        assert_eq!(array, Ok([1, 2]));
+        let evaluated = Cell::new(false);
+        let array: Result<[i32; 3], &str> = try_array![Ok(1), Err("bad"), {
+            evaluated.set(true);
+            Ok(3)
+        }];
+        assert_eq!(array, Err("bad"));
+        assert!(!evaluated.get());
+    }
+
+    #[test]
+    fn try_array_option() {
+        let array: Result<[i32; 2], NoneError> = try_array![Some(1), Some(2),];
+        assert_eq!(array, Ok([1, 2]));
+        let array: Result<[i32; 2], NoneError> = try_array![Some(1), None];
+        assert_eq!(array, Err(NoneError));
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum MixedError {
+        Missing,
+        Parse(ParseIntError),
+    }
+
+    impl From<NoneError> for MixedError {
+        fn from(_: NoneError) -> Self {
+            MixedError::Missing
+        }
+    }
+
+    impl From<ParseIntError> for MixedError {
+        fn from(err: ParseIntError) -> Self {
+            MixedError::Parse(err)
+        }
+    }
+
+    #[test]
+    fn try_array_mixed() {
+        let parse = |s: &str| s.parse::<i32>();
+        let array: Result<[i32; 2], MixedError> = try_array![Some(1), parse("2")];
+        assert_eq!(array, Ok([1, 2]));
+        let array: Result<[i32; 2], MixedError> = try_array![None, parse("2")];
+        assert_eq!(array, Err(MixedError::Missing));
+        let array: Result<[i32; 2], MixedError> = try_array![Some(1), parse("x")];
+        assert_eq!(array, Err(MixedError::Parse(parse("x").unwrap_err())));
+    }
+}
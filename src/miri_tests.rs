@@ -0,0 +1,86 @@
+//! Tests exercising the unsafe code paths, meant to be run under miri with `cargo +nightly miri
+//! test`.
+//!
+//! The elements own heap allocations or count their drops, so miri reports any leak, double drop
+//! or read of uninitialized memory.
+
+extern crate std;
+use crate::{partial_array::PartialArray, TryCollect, TryFromIterator};
+use std::{boxed::Box, rc::Rc, string::String, vec::Vec};
+
+/// An iterator of `n` elements with an unknown length, to bypass the size hint checks.
+fn unhinted<T>(n: usize, f: impl FnMut(usize) -> T) -> impl Iterator<Item = T> {
+    (0..).take_while(move |&i| i < n).map(f)
+}
+
+#[test]
+fn collect_boxes() {
+    let array: [Box<usize>; 4] = unhinted(4, Box::new).try_collect().unwrap();
+    assert_eq!(array.map(|b| *b), [0, 1, 2, 3]);
+    assert!(unhinted(3, Box::new)
+        .try_collect::<[Box<usize>; 4]>()
+        .is_err());
+    assert!(unhinted(5, Box::new)
+        .try_collect::<[Box<usize>; 4]>()
+        .is_err());
+}
+
+#[test]
+fn collect_strings() {
+    let strings = |n| unhinted(n, |i| String::from("x").repeat(i + 1));
+    let array: [String; 3] = strings(3).try_collect().unwrap();
+    assert_eq!(array[2], "xxx");
+    assert!(strings(2).try_collect::<[String; 3]>().is_err());
+    assert!(strings(4).try_collect::<[String; 3]>().is_err());
+}
+
+#[test]
+fn collect_drop_counts() {
+    let rc = Rc::new(());
+    let rcs = |n| unhinted(n, |_| rc.clone());
+    let array: [Rc<()>; 3] = rcs(3).try_collect().unwrap();
+    assert_eq!(Rc::strong_count(&rc), 4);
+    drop(array);
+    assert!(rcs(2).try_collect::<[Rc<()>; 3]>().is_err());
+    assert!(rcs(4).try_collect::<[Rc<()>; 3]>().is_err());
+    assert!(rcs(1).try_collect::<Box<[Rc<()>; 3]>>().is_err());
+    assert!(rcs(4).try_collect::<Box<[Rc<()>; 3]>>().is_err());
+    assert!(rcs(5).try_collect_nested::<2, 2>().is_err());
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn partial_array_partially_filled() {
+    let mut partial = PartialArray::<Box<usize>, 4>::new();
+    partial.extend(unhinted(2, Box::new));
+    assert_eq!(partial.pop().map(|b| *b), Some(1));
+    partial.push(Box::new(5));
+    let clone = partial.clone();
+    drop(partial);
+    let mut iter = clone.into_iter();
+    assert_eq!(iter.next_back().map(|b| *b), Some(5));
+    drop(iter);
+}
+
+#[test]
+fn partial_array_into_array() {
+    let mut partial = PartialArray::<Vec<u8>, 2>::new();
+    partial.push(Vec::from([1]));
+    let mut partial = partial.try_into_array().unwrap_err();
+    partial.push(Vec::from([2, 3]));
+    assert_eq!(partial.into_array(), [Vec::from([1]), Vec::from([2, 3])]);
+}
+
+#[test]
+fn try_extend_exact_keeps_elements_on_error() {
+    let mut partial = PartialArray::<Box<usize>, 3>::new();
+    assert!(
+        <PartialArray<_, 3> as crate::TryExtendExact<_>>::try_extend_exact(
+            &mut partial,
+            unhinted(5, Box::new)
+        )
+        .is_err()
+    );
+    assert_eq!(partial.len(), 3);
+    assert!(<[Box<usize>; 0]>::try_from_iter(unhinted(1, Box::new)).is_err());
+}
@@ -0,0 +1,224 @@
+//! Collecting rayon parallel iterators into arrays.
+//!
+//! ```
+//! use rayon::prelude::*;
+//! use try_collect::parallel::TryParallelCollect;
+//!
+//! let squares: [usize; 1000] = (0..1000usize).into_par_iter().map(|x| x * x).try_collect().unwrap();
+//! assert_eq!(squares[999], 998_001);
+//! ```
+
+use crate::{check_size_hint, partial_array::PartialArray, NonMatchingLenError};
+use rayon::prelude::*;
+
+/// An extension trait for indexed parallel iterators to collect them into arrays.
+pub trait TryParallelCollect: IndexedParallelIterator {
+    /// Collects the items into an array in parallel, failing if the iterator doesn't have
+    /// exactly `N` items.
+    ///
+    /// Since the length of an indexed parallel iterator is known up front, a mismatch is
+    /// reported before any item is produced, with `consumed` set to zero. Each item is written
+    /// straight to the slot at its index, so the elements end up in iterator order. If the
+    /// iterator yields fewer items than its `len()`, this fails with
+    /// [`TooShort`](NonMatchingLenError::TooShort), and if a worker thread panics, the items
+    /// written so far are dropped.
+    fn try_collect<const N: usize>(self) -> Result<[Self::Item; N], NonMatchingLenError> {
+        let len = self.len();
+        check_size_hint(N, (len, Some(len)))?;
+        let mut partial = PartialArray::<Self::Item, N>::new();
+        let base = SlotsPtr(partial.slots_ptr());
+        // Zipping with the indices bounds the writes by `N` even if `len()` is wrong.
+        let run = (0..N)
+            .into_par_iter()
+            .zip(self)
+            .fold(|| Run::empty(base), Run::push)
+            .reduce(|| Run::empty(base), Run::append);
+        if run.start == 0 && run.len == N {
+            core::mem::forget(run);
+            // All `N` slots have been written, and forgetting the run handed them to `partial`.
+            unsafe { partial.set_len(N) };
+            Ok(partial.into_array())
+        } else {
+            Err(NonMatchingLenError::TooShort {
+                expected: N,
+                actual: run.seen,
+                consumed: run.seen,
+            })
+        }
+    }
+}
+
+impl<I: IndexedParallelIterator> TryParallelCollect for I {}
+
+/// Pointer to the slots of the `PartialArray` being filled.
+struct SlotsPtr<T>(*mut T);
+
+impl<T> Clone for SlotsPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SlotsPtr<T> {}
+
+// The slots are only accessed through `Run`s, which own disjoint ranges of them.
+unsafe impl<T: Send> Send for SlotsPtr<T> {}
+unsafe impl<T: Send> Sync for SlotsPtr<T> {}
+
+/// A contiguous range of initialized slots, which is dropped if the collection fails.
+///
+/// Each fold task of rayon handles a contiguous range of indices in order, and `reduce()`
+/// combines adjacent results from left to right, so the runs cover a growing prefix of the
+/// array. Items that don't extend their run are dropped instead of written, and make the
+/// collection fail, so the runs can never overlap even if rayon's ordering assumptions break.
+struct Run<T> {
+    base: SlotsPtr<T>,
+    start: usize,
+    len: usize,
+    /// Number of items received, including dropped ones.
+    seen: usize,
+}
+
+impl<T> Run<T> {
+    fn empty(base: SlotsPtr<T>) -> Self {
+        Self {
+            base,
+            start: 0,
+            len: 0,
+            seen: 0,
+        }
+    }
+
+    fn push(mut self, (index, val): (usize, T)) -> Self {
+        self.seen += 1;
+        if self.len == 0 {
+            self.start = index;
+        }
+        if index == self.start + self.len {
+            // The index is less than `N`, and no other run owns or can claim this slot.
+            unsafe { self.base.0.add(index).write(val) };
+            self.len += 1;
+        }
+        self
+    }
+
+    fn append(mut self, mut other: Self) -> Self {
+        if self.len == 0 {
+            other.seen += self.seen;
+            return other;
+        }
+        self.seen += other.seen;
+        if other.start == self.start + self.len {
+            self.len += other.len;
+            // The slots of `other` now belong to `self`.
+            other.len = 0;
+        }
+        self
+    }
+}
+
+impl<T> Drop for Run<T> {
+    fn drop(&mut self) {
+        // The run owns the initialized slots `start..start + len`.
+        unsafe {
+            let slots = core::ptr::slice_from_raw_parts_mut(self.base.0.add(self.start), self.len);
+            core::ptr::drop_in_place(slots);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use super::TryParallelCollect;
+    use crate::NonMatchingLenError;
+    use rayon::iter::plumbing::{Consumer, ProducerCallback, UnindexedConsumer};
+    use rayon::prelude::*;
+    use std::string::{String, ToString};
+    use std::sync::Arc;
+
+    #[test]
+    fn try_collect_parallel() {
+        let array: [String; 100] = (0..100)
+            .into_par_iter()
+            .map(|x| x.to_string())
+            .try_collect()
+            .unwrap();
+        assert!(array.iter().enumerate().all(|(i, s)| *s == i.to_string()));
+        assert_eq!(
+            (0..5).into_par_iter().try_collect::<4>(),
+            Err(NonMatchingLenError::TooLong {
+                expected: 4,
+                actual: 5,
+                consumed: 0
+            })
+        );
+        assert_eq!(
+            (0..3).into_par_iter().try_collect::<4>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 4,
+                actual: 3,
+                consumed: 0
+            })
+        );
+        let empty: [i32; 0] = (0..0).into_par_iter().try_collect().unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn try_collect_parallel_panic() {
+        let arc = Arc::new(());
+        let result = std::panic::catch_unwind(|| {
+            (0..1000)
+                .into_par_iter()
+                .map(|i| {
+                    assert_ne!(i, 500);
+                    arc.clone()
+                })
+                .try_collect::<1000>()
+        });
+        assert!(result.is_err());
+        assert_eq!(Arc::strong_count(&arc), 1);
+    }
+
+    /// Wrapper reporting one more item than the inner iterator yields.
+    struct Overreported<I>(I);
+
+    impl<I: IndexedParallelIterator> ParallelIterator for Overreported<I> {
+        type Item = I::Item;
+        fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+            self.0.drive_unindexed(consumer)
+        }
+        fn opt_len(&self) -> Option<usize> {
+            Some(self.len())
+        }
+    }
+
+    impl<I: IndexedParallelIterator> IndexedParallelIterator for Overreported<I> {
+        fn len(&self) -> usize {
+            self.0.len() + 1
+        }
+        fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+            self.0.drive(consumer)
+        }
+        fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+            self.0.with_producer(callback)
+        }
+    }
+
+    #[test]
+    fn try_collect_parallel_overreported_len() {
+        let arc = Arc::new(());
+        // The minimum length keeps rayon from splitting the inner producer past its end.
+        let items = (0..3).into_par_iter().map(|_| arc.clone()).with_min_len(8);
+        assert_eq!(
+            Overreported(items).try_collect::<4>(),
+            Err(NonMatchingLenError::TooShort {
+                expected: 4,
+                actual: 3,
+                consumed: 3
+            })
+        );
+        assert_eq!(Arc::strong_count(&arc), 1);
+    }
+}
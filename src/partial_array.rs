@@ -0,0 +1,564 @@
+//! An array that is initialized incrementally.
+//!
+//! [`PartialArray`] is the building block for collecting into arrays, and can be used directly to
+//! fill an array from several sources.
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ops::ControlFlow;
+
+/// A fixed-capacity array that is initialized one element at a time.
+///
+/// Dropping a `PartialArray` drops the elements pushed so far.
+pub struct PartialArray<A, const N: usize> {
+    array: [MaybeUninit<A>; N],
+    len: usize,
+}
+
+impl<A, const N: usize> PartialArray<A, N> {
+    /// The number of elements the array holds when full.
+    pub const CAPACITY: usize = N;
+
+    /// Creates an empty `PartialArray`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            array: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        }
+    }
+
+    /// Appends an element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the array is already full.
+    #[inline]
+    pub fn push(&mut self, val: A) {
+        assert!(self.len < N, "PartialArray already full.");
+        self.array[self.len].write(val);
+        self.len += 1;
+    }
+
+    /// Returns the number of elements pushed so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no elements have been pushed yet.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements that can still be pushed.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        N - self.len
+    }
+
+    /// Returns the number of elements the array holds when full.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if all `N` elements have been pushed.
+    #[inline]
+    pub fn full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Returns the elements pushed so far as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[A] {
+        // The first `len` elements are initialized.
+        unsafe { core::slice::from_raw_parts(self.array.as_ptr().cast::<A>(), self.len) }
+    }
+
+    /// Returns the elements pushed so far as a mutable slice.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [A] {
+        // The first `len` elements are initialized.
+        unsafe { core::slice::from_raw_parts_mut(self.array.as_mut_ptr().cast::<A>(), self.len) }
+    }
+
+    /// Pushes elements from `iter` until it is exhausted or the array is full, and returns the
+    /// number of elements pushed.
+    ///
+    /// No elements are taken from the iterator once the array is full, so the rest of the
+    /// iterator can still be used when passing it by mutable reference.
+    #[inline]
+    pub fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) -> usize {
+        let start = self.len;
+        let mut iter = iter.into_iter();
+        while !self.full() {
+            match iter.next() {
+                Some(val) => self.push(val),
+                None => break,
+            }
+        }
+        self.len - start
+    }
+
+    /// Pushes all elements of `iter` by driving it with `try_fold()`, and returns `true` if the
+    /// iterator had more elements than fit into the array.
+    ///
+    /// Each element is written straight to its slot with a single bounds check. The element that
+    /// overflows the array is dropped.
+    #[inline]
+    pub(crate) fn fill_from<I: Iterator<Item = A>>(&mut self, mut iter: I) -> bool {
+        let Self { array, len } = self;
+        // The index is threaded through the fold so it stays in a register, and `len` is only
+        // written to keep it accurate if the iterator panics.
+        iter.try_fold(*len, |index, val| match array.get_mut(index) {
+            Some(slot) => {
+                slot.write(val);
+                *len = index + 1;
+                ControlFlow::Continue(index + 1)
+            }
+            None => ControlFlow::Break(()),
+        })
+        .is_break()
+    }
+
+    /// Returns a pointer to the first slot, so that the slots can be written from several
+    /// threads at once.
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub(crate) fn slots_ptr(&mut self) -> *mut A {
+        self.array.as_mut_ptr().cast()
+    }
+
+    /// Sets the number of initialized elements.
+    ///
+    /// # Safety
+    ///
+    /// The first `len` slots must be initialized, and `len` must not exceed `N`.
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub(crate) unsafe fn set_len(&mut self, len: usize) {
+        debug_assert!(len <= N);
+        self.len = len;
+    }
+
+    /// Pushes all elements of an iterator that reports its exact length.
+    ///
+    /// The writes are bounded by the capacity even if the iterator yields more elements than
+    /// it reports, so a faulty `TrustedLen` impl can't cause writes out of bounds.
+    #[cfg(feature = "trusted_len")]
+    #[inline]
+    pub(crate) fn extend_trusted<I: core::iter::TrustedLen<Item = A>>(&mut self, iter: I) {
+        let len = &mut self.len;
+        for (slot, val) in self.array[*len..].iter_mut().zip(iter) {
+            slot.write(val);
+            *len += 1;
+        }
+    }
+
+    /// Removes the last element and returns it, or `None` if the array is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<A> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // The slot was initialized, and decrementing `len` hands its ownership to us.
+        Some(unsafe { self.array[self.len].assume_init_read() })
+    }
+
+    /// Converts into the fully initialized array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the array is not full yet.
+    #[inline]
+    pub fn into_array(self) -> [A; N] {
+        assert!(self.full(), "PartialArray not yet fully initialized.");
+        // We just checked that the array is full.
+        unsafe { self.into_array_unchecked() }
+    }
+
+    /// Converts into the array without checking that it is full.
+    ///
+    /// # Safety
+    ///
+    /// The array must be full, i.e. all `N` elements must have been pushed. This is only checked
+    /// in debug builds.
+    #[inline]
+    pub unsafe fn into_array_unchecked(self) -> [A; N] {
+        debug_assert!(self.full(), "PartialArray not yet fully initialized.");
+        let this = ManuallyDrop::new(self);
+        // Wrapping `self` in `ManuallyDrop` before moving the array out hands ownership of the
+        // elements to the result, and the caller guarantees that all elements are initialized.
+        #[cfg(feature = "array_assume_init")]
+        unsafe {
+            MaybeUninit::array_assume_init(core::ptr::read(&this.array))
+        }
+        // `[MaybeUninit<A>; N]` has the same layout as `[A; N]`, so the array can be read out
+        // through a cast pointer. We can't `transmute()` arrays of generic length.
+        #[cfg(not(feature = "array_assume_init"))]
+        unsafe {
+            (&this.array as *const [MaybeUninit<A>; N])
+                .cast::<[A; N]>()
+                .read()
+        }
+    }
+
+    /// Converts into the fully initialized array, or gives back `self` if it is not full yet.
+    #[inline]
+    pub fn try_into_array(self) -> Result<[A; N], Self> {
+        if self.full() {
+            Ok(self.into_array())
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Converts into the raw, partially initialized array and the number of initialized elements.
+    ///
+    /// **The caller becomes responsible for dropping the first `len` elements.** They are not
+    /// dropped automatically anymore, so they are leaked unless the caller drops them, e.g. with
+    /// `MaybeUninit::assume_init_drop()`.
+    #[inline]
+    pub fn into_uninit(self) -> ([MaybeUninit<A>; N], usize) {
+        let len = self.len;
+        let this = ManuallyDrop::new(self);
+        // Moving the array out is safe since `this` is never dropped.
+        (unsafe { core::ptr::read(&this.array) }, len)
+    }
+}
+
+impl<A, const N: usize> PartialArray<A, N> {
+    /// Moves the elements pushed so far into a `Vec`.
+    #[cfg(feature = "alloc")]
+    pub fn into_vec(self) -> alloc::vec::Vec<A> {
+        self.into_iter().collect()
+    }
+}
+
+impl<A, const N: usize> Default for PartialArray<A, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Clone, const N: usize> Clone for PartialArray<A, N> {
+    fn clone(&self) -> Self {
+        let mut clone = Self::new();
+        clone.extend(self.as_slice().iter().cloned());
+        clone
+    }
+}
+
+impl<A: fmt::Debug, const N: usize> fmt::Debug for PartialArray<A, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PartialArray")
+            .field("len", &self.len)
+            .field("capacity", &N)
+            .field("initialized", &self.as_slice())
+            .finish()
+    }
+}
+
+impl<A, const N: usize> Drop for PartialArray<A, N> {
+    fn drop(&mut self) {
+        for i in 0..self.len {
+            unsafe {
+                // We can't use `assume_init()`, since we don't have ownership of the values.
+                // We can't use `assume_init_drop()`, since it's unstable.
+                core::ptr::drop_in_place(self.array[i].as_mut_ptr());
+            }
+        }
+    }
+}
+
+impl<A, const N: usize> IntoIterator for PartialArray<A, N> {
+    type Item = A;
+    type IntoIter = IntoIter<A, N>;
+
+    /// Returns an iterator that moves out the elements pushed so far.
+    fn into_iter(self) -> Self::IntoIter {
+        let this = ManuallyDrop::new(self);
+        IntoIter {
+            // Moving the array out is safe since `this` is never dropped.
+            array: unsafe { core::ptr::read(&this.array) },
+            start: 0,
+            end: this.len,
+        }
+    }
+}
+
+/// An iterator that moves out of a [`PartialArray`].
+///
+/// Dropping the iterator drops the elements that haven't been yielded yet.
+pub struct IntoIter<A, const N: usize> {
+    array: [MaybeUninit<A>; N],
+    // The elements in `start..end` are initialized and not yet yielded.
+    start: usize,
+    end: usize,
+}
+
+impl<A, const N: usize> Iterator for IntoIter<A, N> {
+    type Item = A;
+
+    fn next(&mut self) -> Option<A> {
+        if self.start == self.end {
+            return None;
+        }
+        self.start += 1;
+        // The slot is initialized, and advancing `start` hands its ownership to us.
+        Some(unsafe { self.array[self.start - 1].assume_init_read() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<A, const N: usize> DoubleEndedIterator for IntoIter<A, N> {
+    fn next_back(&mut self) -> Option<A> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        // The slot is initialized, and decrementing `end` hands its ownership to us.
+        Some(unsafe { self.array[self.end].assume_init_read() })
+    }
+}
+
+impl<A, const N: usize> ExactSizeIterator for IntoIter<A, N> {}
+
+impl<A, const N: usize> FusedIterator for IntoIter<A, N> {}
+
+impl<A, const N: usize> Drop for IntoIter<A, N> {
+    fn drop(&mut self) {
+        for i in self.start..self.end {
+            unsafe {
+                core::ptr::drop_in_place(self.array[i].as_mut_ptr());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use super::PartialArray;
+    use std::{cell::RefCell, format, vec, vec::Vec};
+
+    #[test]
+    #[should_panic]
+    fn partial_array_not_full() {
+        let mut partial = PartialArray::<i32, 3>::new();
+        partial.push(1);
+        partial.push(2);
+        partial.into_array();
+    }
+
+    #[test]
+    fn partial_array_const_new() {
+        const EMPTY: PartialArray<u8, 16> = PartialArray::new();
+        let partial = const { PartialArray::<u8, 4>::new() };
+        assert!(EMPTY.is_empty());
+        assert!(partial.is_empty());
+    }
+
+    #[test]
+    fn partial_array_try_into_array() {
+        let mut partial = PartialArray::<i32, 2>::new();
+        partial.push(1);
+        let mut partial = partial.try_into_array().unwrap_err();
+        partial.push(2);
+        assert_eq!(partial.try_into_array().ok(), Some([1, 2]));
+    }
+
+    #[test]
+    fn partial_array_len() {
+        let mut partial = PartialArray::<i32, 3>::new();
+        assert!(partial.is_empty());
+        assert_eq!(partial.remaining(), 3);
+        partial.push(1);
+        assert!(!partial.is_empty());
+        assert_eq!(partial.len(), 1);
+        assert_eq!(partial.remaining(), 2);
+        assert_eq!(partial.capacity(), PartialArray::<i32, 3>::CAPACITY);
+    }
+
+    #[test]
+    fn partial_array_as_slice() {
+        let mut partial = PartialArray::<i32, 3>::new();
+        assert!(partial.as_slice().is_empty());
+        partial.push(1);
+        partial.push(2);
+        assert_eq!(partial.as_slice(), [1, 2]);
+        partial.as_mut_slice()[1] = 5;
+        assert_eq!(partial.as_mut_slice(), [1, 5]);
+    }
+
+    #[test]
+    fn partial_array_extend() {
+        let mut partial = PartialArray::<i32, 5>::new();
+        assert_eq!(partial.extend([1, 2]), 2);
+        let mut iter = 3..10;
+        assert_eq!(partial.extend(&mut iter), 3);
+        assert_eq!(iter.next(), Some(6));
+        assert_eq!(partial.extend(&mut iter), 0);
+        assert_eq!(iter.next(), Some(7));
+        assert_eq!(partial.into_array(), [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn partial_array_too_full() {
+        let mut partial = PartialArray::<i32, 2>::new();
+        partial.push(1);
+        partial.push(2);
+        partial.push(3);
+    }
+
+    #[derive(Clone)]
+    struct Guard<'a> {
+        index: usize,
+        log: &'a RefCell<Vec<usize>>,
+    }
+
+    impl Drop for Guard<'_> {
+        fn drop(&mut self) {
+            self.log.borrow_mut().push(self.index);
+        }
+    }
+
+    #[test]
+    fn partial_array_drop() {
+        let drop_log = RefCell::new(vec![]);
+        let guard = |i| Guard {
+            index: i,
+            log: &drop_log,
+        };
+        let mut partial = PartialArray::<Guard, 3>::new();
+        partial.push(guard(0));
+        partial.push(guard(1));
+        partial.push(guard(2));
+        let array = partial.into_array();
+        assert!(drop_log.borrow().is_empty());
+        drop(array);
+        assert_eq!(&*drop_log.borrow(), &[0, 1, 2]);
+    }
+
+    #[test]
+    fn partial_array_pop() {
+        let drop_log = RefCell::new(vec![]);
+        let guard = |i| Guard {
+            index: i,
+            log: &drop_log,
+        };
+        let mut partial = PartialArray::<Guard, 3>::new();
+        partial.push(guard(0));
+        partial.push(guard(1));
+        let popped = partial.pop().unwrap();
+        assert_eq!(popped.index, 1);
+        assert!(drop_log.borrow().is_empty());
+        drop(popped);
+        assert_eq!(&*drop_log.borrow(), &[1]);
+        partial.push(guard(2));
+        drop(partial);
+        assert_eq!(&*drop_log.borrow(), &[1, 0, 2]);
+    }
+
+    #[test]
+    fn partial_array_pop_empty() {
+        let mut partial = PartialArray::<i32, 1>::new();
+        assert_eq!(partial.pop(), None);
+        partial.push(1);
+        assert_eq!(partial.pop(), Some(1));
+        assert_eq!(partial.pop(), None);
+    }
+
+    #[test]
+    fn partial_array_into_array_unchecked() {
+        let mut partial = PartialArray::<_, 2>::new();
+        partial.extend(1..);
+        assert_eq!(unsafe { partial.into_array_unchecked() }, [1, 2]);
+    }
+
+    #[test]
+    fn partial_array_into_uninit() {
+        let log = RefCell::new(vec![]);
+        let mut partial = PartialArray::<_, 3>::new();
+        partial.push(Guard {
+            index: 0,
+            log: &log,
+        });
+        partial.push(Guard {
+            index: 1,
+            log: &log,
+        });
+        let (mut array, len) = partial.into_uninit();
+        assert_eq!(len, 2);
+        assert!(log.borrow().is_empty());
+        for slot in &mut array[..len] {
+            unsafe { slot.assume_init_drop() };
+        }
+        assert_eq!(*log.borrow(), [0, 1]);
+    }
+
+    #[test]
+    fn partial_array_into_iter() {
+        let mut partial = PartialArray::<i32, 5>::new();
+        partial.extend([1, 2, 3, 4]);
+        let mut iter = partial.into_iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next_back(), Some(4));
+        assert_eq!(iter.collect::<Vec<_>>(), [2, 3]);
+    }
+
+    #[test]
+    fn partial_array_into_iter_drop() {
+        let drop_log = RefCell::new(vec![]);
+        let guard = |i| Guard {
+            index: i,
+            log: &drop_log,
+        };
+        let mut partial = PartialArray::<Guard, 4>::new();
+        partial.extend((0..3).map(guard));
+        let mut iter = partial.into_iter();
+        let first = iter.next().unwrap();
+        assert!(drop_log.borrow().is_empty());
+        drop(iter);
+        assert_eq!(&*drop_log.borrow(), &[1, 2]);
+        drop(first);
+        assert_eq!(&*drop_log.borrow(), &[1, 2, 0]);
+    }
+
+    #[test]
+    fn partial_array_clone() {
+        let drop_log = RefCell::new(vec![]);
+        let guard = |i| Guard {
+            index: i,
+            log: &drop_log,
+        };
+        let mut partial = PartialArray::<Guard, 3>::new();
+        partial.extend((0..2).map(guard));
+        let clone = partial.clone();
+        assert_eq!(clone.len(), 2);
+        drop(partial);
+        assert_eq!(&*drop_log.borrow(), &[0, 1]);
+        drop(clone);
+        assert_eq!(&*drop_log.borrow(), &[0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn partial_array_debug() {
+        let mut partial = PartialArray::<i32, 3>::new();
+        partial.extend([1, 2]);
+        assert_eq!(
+            format!("{:?}", partial),
+            "PartialArray { len: 2, capacity: 3, initialized: [1, 2] }"
+        );
+    }
+}
@@ -0,0 +1,94 @@
+//! Deserializing sequences into arrays of an exact length.
+//!
+//! ```
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Point {
+//!     #[serde(deserialize_with = "try_collect::serde_array::deserialize_array")]
+//!     coords: [f64; 3],
+//! }
+//!
+//! let point: Point = serde_json::from_str(r#"{"coords": [1.0, 2.0, 3.0]}"#).unwrap();
+//! assert_eq!(point.coords, [1.0, 2.0, 3.0]);
+//! let err = serde_json::from_str::<Point>(r#"{"coords": [1.0, 2.0]}"#).err().unwrap();
+//! assert!(err.to_string().starts_with("invalid length 2, expected an array of length 3"));
+//! ```
+
+use crate::partial_array::PartialArray;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{Deserialize, Deserializer, Error, IgnoredAny, SeqAccess, Visitor};
+
+/// Deserializes a sequence of exactly `N` elements into an array.
+///
+/// If the sequence has the wrong length, this fails with [`Error::invalid_length()`], giving the
+/// actual length and the expected one. For sequences that are too long, the remaining elements
+/// are skipped to determine the actual length.
+pub fn deserialize_array<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    deserializer.deserialize_seq(ArrayVisitor(PhantomData))
+}
+
+struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>, const N: usize> Visitor<'de> for ArrayVisitor<T, N> {
+    type Value = [T; N];
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an array of length {}", N)
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let mut partial = PartialArray::new();
+        while !partial.full() {
+            match seq.next_element()? {
+                Some(val) => partial.push(val),
+                None => return Err(S::Error::invalid_length(partial.len(), &self)),
+            }
+        }
+        let mut extra = 0;
+        while seq.next_element::<IgnoredAny>()?.is_some() {
+            extra += 1;
+        }
+        if extra > 0 {
+            return Err(S::Error::invalid_length(N + extra, &self));
+        }
+        Ok(partial.into_array())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use serde::Deserialize;
+    use std::string::{String, ToString};
+
+    #[derive(Debug, Deserialize)]
+    struct Triple {
+        #[serde(deserialize_with = "super::deserialize_array")]
+        values: [String; 3],
+    }
+
+    fn parse(json: &str) -> Result<Triple, String> {
+        serde_json::from_str(json).map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn deserialize_array() {
+        let triple = parse(r#"{"values": ["a", "b", "c"]}"#).unwrap();
+        assert_eq!(triple.values, ["a", "b", "c"]);
+        assert_eq!(
+            parse(r#"{"values": ["a", "b"]}"#).unwrap_err(),
+            "invalid length 2, expected an array of length 3 at line 1 column 21"
+        );
+        assert_eq!(
+            parse(r#"{"values": ["a", "b", "c", "d", "e"]}"#).unwrap_err(),
+            "invalid length 5, expected an array of length 3 at line 1 column 36"
+        );
+        assert!(parse(r#"{"values": ["a", "b", 3]}"#).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+//! Collecting the items of a [`Stream`] into an array.
+//!
+//! ```
+//! use futures::{executor::block_on, stream};
+//! use try_collect::stream::TryCollectStream;
+//!
+//! let array = block_on(stream::iter(1..4).try_collect::<3>());
+//! assert_eq!(array, Ok([1, 2, 3]));
+//! assert!(block_on(stream::iter(1..4).try_collect::<2>()).is_err());
+//! ```
+
+use crate::{check_size_hint, partial_array::PartialArray, NonMatchingLenError};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+
+/// An extension trait for streams to collect them into arrays.
+pub trait TryCollectStream: Stream {
+    /// Collects the items of the stream into an array, failing if the stream doesn't yield
+    /// exactly `N` items.
+    ///
+    /// The semantics are the same as for [`TryCollect::try_collect()`](crate::TryCollect). If the
+    /// returned future is dropped before it completes, the items collected so far are dropped
+    /// with it.
+    fn try_collect<const N: usize>(self) -> TryCollectArray<Self, N>
+    where
+        Self: Sized,
+    {
+        TryCollectArray {
+            stream: self,
+            partial: PartialArray::new(),
+        }
+    }
+}
+
+impl<S: Stream + ?Sized> TryCollectStream for S {}
+
+/// Future returned by [`TryCollectStream::try_collect()`].
+#[must_use = "futures do nothing unless polled"]
+pub struct TryCollectArray<S: Stream, const N: usize> {
+    stream: S,
+    partial: PartialArray<S::Item, N>,
+}
+
+impl<S: Stream, const N: usize> Future for TryCollectArray<S, N> {
+    type Output = Result<[S::Item; N], NonMatchingLenError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // `stream` is structurally pinned: it is never moved out of `self`, there is no `Drop`
+        // impl, and `Self` is only `Unpin` if `S` is. `partial` is never pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+        if this.partial.is_empty() {
+            if let Err(err) = check_size_hint(N, stream.size_hint()) {
+                return Poll::Ready(Err(err));
+            }
+        }
+        loop {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Some(val)) => {
+                    if this.partial.full() {
+                        return Poll::Ready(Err(NonMatchingLenError::TooLong {
+                            expected: N,
+                            actual: N + 1,
+                            consumed: N + 1,
+                        }));
+                    }
+                    this.partial.push(val);
+                }
+                Poll::Ready(None) => {
+                    let partial = core::mem::take(&mut this.partial);
+                    return Poll::Ready(partial.try_into_array().map_err(|partial| {
+                        NonMatchingLenError::TooShort {
+                            expected: N,
+                            actual: partial.len(),
+                            consumed: partial.len(),
+                        }
+                    }));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use super::TryCollectStream;
+    use crate::NonMatchingLenError;
+    use core::task::Poll;
+    use futures::executor::block_on;
+    use futures::{stream, FutureExt, StreamExt};
+    use std::rc::Rc;
+
+    #[test]
+    fn try_collect_stream() {
+        let unhinted = |n| stream::iter(1..).take_while(move |&x| core::future::ready(x <= n));
+        assert_eq!(block_on(unhinted(3).try_collect()), Ok([1, 2, 3]));
+        assert_eq!(
+            block_on(unhinted(3).try_collect::<2>()),
+            Err(NonMatchingLenError::TooLong {
+                expected: 2,
+                actual: 3,
+                consumed: 3
+            })
+        );
+        assert_eq!(
+            block_on(unhinted(3).try_collect::<4>()),
+            Err(NonMatchingLenError::TooShort {
+                expected: 4,
+                actual: 3,
+                consumed: 3
+            })
+        );
+        assert_eq!(
+            block_on(stream::iter(1..4).try_collect::<4>()),
+            Err(NonMatchingLenError::TooShort {
+                expected: 4,
+                actual: 3,
+                consumed: 0
+            })
+        );
+    }
+
+    #[test]
+    fn try_collect_stream_cancelled() {
+        let rc = Rc::new(());
+        let items =
+            stream::iter([rc.clone(), rc.clone()]).chain(stream::poll_fn(|_| Poll::Pending));
+        let future = items.try_collect::<3>();
+        assert_eq!(future.now_or_never(), None);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+}
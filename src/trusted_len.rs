@@ -0,0 +1,45 @@
+//! A specialized fill loop for iterators implementing the unstable `TrustedLen` trait.
+
+use crate::{extend_exact, partial_array::PartialArray, NonMatchingLenError};
+use core::iter::TrustedLen;
+
+#[inline]
+pub(crate) fn spec_extend_exact<A, I, const N: usize>(
+    partial: &mut PartialArray<A, N>,
+    iter: I,
+) -> Result<(), NonMatchingLenError>
+where
+    I: Iterator<Item = A>,
+{
+    SpecExtendExact::spec_extend_exact(partial, iter)
+}
+
+trait SpecExtendExact<I> {
+    fn spec_extend_exact(&mut self, iter: I) -> Result<(), NonMatchingLenError>;
+}
+
+impl<A, I: Iterator<Item = A>, const N: usize> SpecExtendExact<I> for PartialArray<A, N> {
+    #[inline]
+    default fn spec_extend_exact(&mut self, iter: I) -> Result<(), NonMatchingLenError> {
+        extend_exact(self, iter)
+    }
+}
+
+impl<A, I: TrustedLen<Item = A>, const N: usize> SpecExtendExact<I> for PartialArray<A, N> {
+    #[inline]
+    fn spec_extend_exact(&mut self, iter: I) -> Result<(), NonMatchingLenError> {
+        // The size hint has already been checked against the remaining capacity, and it is exact
+        // for `TrustedLen` iterators, so the iterator fits exactly without any further checks.
+        let start = self.len();
+        self.extend_trusted(iter);
+        if !self.full() {
+            // Only reachable with a faulty `TrustedLen` impl.
+            return Err(NonMatchingLenError::TooShort {
+                expected: N - start,
+                actual: self.len() - start,
+                consumed: self.len() - start,
+            });
+        }
+        Ok(())
+    }
+}
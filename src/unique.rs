@@ -0,0 +1,262 @@
+//! Maps and sets that reject duplicates when collected.
+//!
+//! The standard `FromIterator` impls for maps and sets silently overwrite or merge duplicates.
+//! Collecting into the wrappers in this module fails on the first duplicate instead.
+
+use crate::TryFromIterator;
+use alloc::collections::BTreeMap;
+use core::{fmt, ops::Deref};
+#[cfg(feature = "std")]
+use {
+    core::hash::Hash,
+    std::collections::{HashMap, HashSet},
+};
+
+/// Error returned when collecting a map encounters the same key twice.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DuplicateKeyError<K> {
+    key: K,
+}
+
+impl<K> DuplicateKeyError<K> {
+    /// The duplicate key.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Returns the duplicate key.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+}
+
+impl<K: fmt::Debug> fmt::Display for DuplicateKeyError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "duplicate key {:?}", self.key)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: fmt::Debug> std::error::Error for DuplicateKeyError<K> {}
+
+#[cfg(all(feature = "error_in_core", not(feature = "std")))]
+impl<K: fmt::Debug> core::error::Error for DuplicateKeyError<K> {}
+
+/// Error returned when collecting a set encounters the same value twice.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DuplicateValueError<T> {
+    value: T,
+}
+
+impl<T> DuplicateValueError<T> {
+    /// The duplicate value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the duplicate value.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+impl<T: fmt::Debug> fmt::Display for DuplicateValueError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "duplicate value {:?}", self.value)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> std::error::Error for DuplicateValueError<T> {}
+
+#[cfg(all(feature = "error_in_core", not(feature = "std")))]
+impl<T: fmt::Debug> core::error::Error for DuplicateValueError<T> {}
+
+/// A `HashMap` that fails to collect if a key occurs more than once.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct UniqueHashMap<K, V>(HashMap<K, V>);
+
+#[cfg(feature = "std")]
+impl<K, V> Default for UniqueHashMap<K, V> {
+    fn default() -> Self {
+        Self(HashMap::new())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V> UniqueHashMap<K, V> {
+    /// Converts into the underlying `HashMap`.
+    pub fn into_inner(self) -> HashMap<K, V> {
+        self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V> Deref for UniqueHashMap<K, V> {
+    type Target = HashMap<K, V>;
+    fn deref(&self) -> &HashMap<K, V> {
+        &self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Hash + Eq, V> TryFromIterator<(K, V)> for UniqueHashMap<K, V> {
+    type Error = DuplicateKeyError<K>;
+    fn try_from_iter<T>(iter: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        let iter = iter.into_iter();
+        let mut map = HashMap::with_capacity(iter.size_hint().0);
+        for (key, val) in iter {
+            if map.contains_key(&key) {
+                return Err(DuplicateKeyError { key });
+            }
+            map.insert(key, val);
+        }
+        Ok(Self(map))
+    }
+}
+
+/// A `BTreeMap` that fails to collect if a key occurs more than once.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UniqueBTreeMap<K, V>(BTreeMap<K, V>);
+
+impl<K, V> Default for UniqueBTreeMap<K, V> {
+    fn default() -> Self {
+        Self(BTreeMap::new())
+    }
+}
+
+impl<K, V> UniqueBTreeMap<K, V> {
+    /// Converts into the underlying `BTreeMap`.
+    pub fn into_inner(self) -> BTreeMap<K, V> {
+        self.0
+    }
+}
+
+impl<K, V> Deref for UniqueBTreeMap<K, V> {
+    type Target = BTreeMap<K, V>;
+    fn deref(&self) -> &BTreeMap<K, V> {
+        &self.0
+    }
+}
+
+impl<K: Ord, V> TryFromIterator<(K, V)> for UniqueBTreeMap<K, V> {
+    type Error = DuplicateKeyError<K>;
+    fn try_from_iter<T>(iter: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        let mut map = BTreeMap::new();
+        for (key, val) in iter {
+            if map.contains_key(&key) {
+                return Err(DuplicateKeyError { key });
+            }
+            map.insert(key, val);
+        }
+        Ok(Self(map))
+    }
+}
+
+/// A `HashSet` that fails to collect if a value occurs more than once.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct UniqueHashSet<T>(HashSet<T>);
+
+#[cfg(feature = "std")]
+impl<T> Default for UniqueHashSet<T> {
+    fn default() -> Self {
+        Self(HashSet::new())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> UniqueHashSet<T> {
+    /// Converts into the underlying `HashSet`.
+    pub fn into_inner(self) -> HashSet<T> {
+        self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Deref for UniqueHashSet<T> {
+    type Target = HashSet<T>;
+    fn deref(&self) -> &HashSet<T> {
+        &self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Hash + Eq> TryFromIterator<T> for UniqueHashSet<T> {
+    type Error = DuplicateValueError<T>;
+    fn try_from_iter<I>(iter: I) -> Result<Self, Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        let mut set = HashSet::with_capacity(iter.size_hint().0);
+        for value in iter {
+            if set.contains(&value) {
+                return Err(DuplicateValueError { value });
+            }
+            set.insert(value);
+        }
+        Ok(Self(set))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UniqueBTreeMap;
+    #[cfg(feature = "std")]
+    use super::{UniqueHashMap, UniqueHashSet};
+    use crate::TryCollect;
+
+    struct NonDefault;
+
+    #[test]
+    fn unique_collections_default() {
+        assert!(UniqueBTreeMap::<NonDefault, NonDefault>::default().is_empty());
+        #[cfg(feature = "std")]
+        assert!(UniqueHashMap::<NonDefault, NonDefault>::default().is_empty());
+        #[cfg(feature = "std")]
+        assert!(UniqueHashSet::<NonDefault>::default().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn try_collect_unique_hash_map() {
+        let map: UniqueHashMap<_, _> = [(1, "a"), (2, "b")].into_iter().try_collect().unwrap();
+        assert_eq!(map[&2], "b");
+        let err = [(1, "a"), (2, "b"), (1, "c")]
+            .into_iter()
+            .try_collect::<UniqueHashMap<_, _>>()
+            .unwrap_err();
+        assert_eq!(*err.key(), 1);
+    }
+
+    #[test]
+    fn try_collect_unique_btree_map() {
+        let map: UniqueBTreeMap<_, _> = [("b", 2), ("a", 1)].into_iter().try_collect().unwrap();
+        assert!(map.keys().eq(&["a", "b"]));
+        let err = [("a", 1), ("a", 2)]
+            .into_iter()
+            .try_collect::<UniqueBTreeMap<_, _>>()
+            .unwrap_err();
+        assert_eq!(err.into_key(), "a");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn try_collect_unique_hash_set() {
+        let set: UniqueHashSet<_> = "abc".chars().try_collect().unwrap();
+        assert!(set.contains(&'b'));
+        let err = "abca"
+            .chars()
+            .try_collect::<UniqueHashSet<_>>()
+            .unwrap_err();
+        assert_eq!(*err.value(), 'a');
+    }
+}